@@ -0,0 +1,36 @@
+use std::{env, fs, path::Path};
+
+/// With the `embed-inputs` feature, generates `embedded_inputs.rs` mapping every `data/*.txt`
+/// path to its content, see `file_utils`.
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_none() {
+        return;
+    }
+    println!("cargo:rerun-if-changed=data");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let data_dir = Path::new(&manifest_dir).join("data");
+
+    let mut files: Vec<_> = fs::read_dir(&data_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    files.sort();
+
+    let mut generated = String::from("pub static EMBEDDED_INPUTS: &[(&str, &str)] = &[\n");
+    for file in files {
+        println!("cargo:rerun-if-changed={}", file.display());
+        let name = file.file_name().unwrap().to_str().unwrap();
+        generated.push_str(&format!(
+            "    ({:?}, include_str!({:?})),\n",
+            format!("data/{}", name),
+            file.display().to_string()
+        ));
+    }
+    generated.push_str("];\n");
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("embedded_inputs.rs");
+    fs::write(out_path, generated).unwrap();
+}
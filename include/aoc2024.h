@@ -0,0 +1,31 @@
+/* Generated from src/ffi.rs, do not edit. */
+#ifndef AOC2024_H
+#define AOC2024_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#define AOC_OK 0 /* ok */
+#define AOC_ERR_NULL_POINTER 1 /* null pointer argument */
+#define AOC_ERR_INVALID_UTF8 2 /* input is not valid UTF-8 */
+#define AOC_ERR_UNKNOWN_TASK 3 /* no such day and part */
+#define AOC_ERR_SOLVER_FAILED 4 /* solver returned an error */
+#define AOC_ERR_SOLVER_PANICKED 5 /* solver panicked, is the input valid? */
+#define AOC_ERR_BUFFER_TOO_SMALL 6 /* output buffer is too small */
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+int32_t aoc_solve(uint32_t day, uint32_t part, const uint8_t *input_ptr,
+                  size_t input_len, int64_t *out_buf);
+
+int32_t aoc_list_tasks(char *out_buf, size_t buf_len, size_t *out_len);
+
+const char *aoc_error_message(int32_t code);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* AOC2024_H */
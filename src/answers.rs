@@ -0,0 +1,245 @@
+use std::{
+    error::Error,
+    fmt,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+pub const DEFAULT_ANSWERS_PATH: &str = "data/answers.txt";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Accepted,
+    Wrong(Option<Hint>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnswerRecord {
+    pub task_id: String,
+    pub answer: i64,
+    pub verdict: Verdict,
+}
+
+impl AnswerRecord {
+    /// Parses a single `<task_id> <answer> accepted|wrong [too_high|too_low]` line.
+    fn from_line(line: &str) -> Result<Self, Box<dyn Error>> {
+        let mut parts = line.split_whitespace();
+        let task_id = parts.next().ok_or("task_id not found in line")?.to_string();
+        let answer: i64 = parts.next().ok_or("answer not found in line")?.parse()?;
+        let verdict = match (parts.next(), parts.next()) {
+            (Some("accepted"), None) => Verdict::Accepted,
+            (Some("wrong"), None) => Verdict::Wrong(None),
+            (Some("wrong"), Some("too_high")) => Verdict::Wrong(Some(Hint::TooHigh)),
+            (Some("wrong"), Some("too_low")) => Verdict::Wrong(Some(Hint::TooLow)),
+            _ => return Err(format!("invalid verdict in line: {}", line).into()),
+        };
+
+        Ok(AnswerRecord {
+            task_id,
+            answer,
+            verdict,
+        })
+    }
+}
+
+impl fmt::Display for AnswerRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} ", self.task_id, self.answer)?;
+        match self.verdict {
+            Verdict::Accepted => write!(f, "accepted"),
+            Verdict::Wrong(None) => write!(f, "wrong"),
+            Verdict::Wrong(Some(Hint::TooHigh)) => write!(f, "wrong too_high"),
+            Verdict::Wrong(Some(Hint::TooLow)) => write!(f, "wrong too_low"),
+        }
+    }
+}
+
+/// Reasons for refusing to submit an answer without asking the server.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadyAccepted(i64),
+    AlreadyRejected(i64),
+    TooHigh { known_too_high: i64 },
+    TooLow { known_too_low: i64 },
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::AlreadyAccepted(answer) => write!(f, "task already solved with {}", answer),
+            Refusal::AlreadyRejected(answer) => write!(f, "{} was already rejected", answer),
+            Refusal::TooHigh { known_too_high } => {
+                write!(f, "answer must be lower than {}", known_too_high)
+            }
+            Refusal::TooLow { known_too_low } => {
+                write!(f, "answer must be higher than {}", known_too_low)
+            }
+        }
+    }
+}
+
+impl Error for Refusal {}
+
+/// Append-only record of submitted answers and the server verdicts.
+pub struct AnswersStore {
+    path: PathBuf,
+    records: Vec<AnswerRecord>,
+}
+
+impl AnswersStore {
+    /// Loads the store, a missing file is treated as an empty store.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        let path = path.as_ref().to_path_buf();
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err.into()),
+        };
+
+        let records = content
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .map(AnswerRecord::from_line)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(AnswersStore { path, records })
+    }
+
+    #[cfg(test)]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn records_for<'a>(&'a self, task_id: &'a str) -> impl Iterator<Item = &'a AnswerRecord> {
+        self.records.iter().filter(move |r| r.task_id == task_id)
+    }
+
+    pub fn accepted(&self, task_id: &str) -> Option<i64> {
+        self.records_for(task_id)
+            .find(|r| r.verdict == Verdict::Accepted)
+            .map(|r| r.answer)
+    }
+
+    /// Checks the answer against everything we already know about the task.
+    pub fn check(&self, task_id: &str, answer: i64) -> Result<(), Refusal> {
+        if let Some(accepted) = self.accepted(task_id) {
+            return Err(Refusal::AlreadyAccepted(accepted));
+        }
+
+        let mut lowest_too_high: Option<i64> = None;
+        let mut highest_too_low: Option<i64> = None;
+
+        for record in self.records_for(task_id) {
+            if record.answer == answer {
+                return Err(Refusal::AlreadyRejected(answer));
+            }
+
+            match record.verdict {
+                Verdict::Wrong(Some(Hint::TooHigh)) => {
+                    lowest_too_high =
+                        Some(lowest_too_high.map_or(record.answer, |v| v.min(record.answer)))
+                }
+                Verdict::Wrong(Some(Hint::TooLow)) => {
+                    highest_too_low =
+                        Some(highest_too_low.map_or(record.answer, |v| v.max(record.answer)))
+                }
+                _ => (),
+            }
+        }
+
+        if let Some(known_too_high) = lowest_too_high.filter(|&v| answer >= v) {
+            return Err(Refusal::TooHigh { known_too_high });
+        }
+
+        if let Some(known_too_low) = highest_too_low.filter(|&v| answer <= v) {
+            return Err(Refusal::TooLow { known_too_low });
+        }
+
+        Ok(())
+    }
+
+    /// Appends the record to both memory and the backing file.
+    pub fn record(&mut self, record: AnswerRecord) -> Result<(), Box<dyn Error>> {
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", record)?;
+
+        self.records.push(record);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store_with(content: &str) -> AnswersStore {
+        let records = content
+            .lines()
+            .map(|l| AnswerRecord::from_line(l).unwrap())
+            .collect();
+        AnswersStore {
+            path: PathBuf::new(),
+            records,
+        }
+    }
+
+    #[test]
+    fn record_roundtrip_test() {
+        for line in [
+            "day1_task1 11 accepted",
+            "day1_task2 12 wrong",
+            "day1_task2 13 wrong too_high",
+            "day1_task2 14 wrong too_low",
+        ] {
+            assert_eq!(line, AnswerRecord::from_line(line).unwrap().to_string());
+        }
+
+        assert!(AnswerRecord::from_line("day1_task1 11 maybe").is_err());
+    }
+
+    #[test]
+    fn check_test() {
+        let store = store_with(
+            "day1_task1 100 wrong too_high\n\
+             day1_task1 10 wrong too_low\n\
+             day1_task1 50 wrong\n\
+             day1_task2 7 accepted",
+        );
+
+        assert_eq!(Ok(()), store.check("day1_task1", 42));
+        assert_eq!(
+            Err(Refusal::AlreadyRejected(50)),
+            store.check("day1_task1", 50)
+        );
+        assert_eq!(
+            Err(Refusal::TooHigh {
+                known_too_high: 100
+            }),
+            store.check("day1_task1", 150)
+        );
+        assert_eq!(
+            Err(Refusal::TooLow { known_too_low: 10 }),
+            store.check("day1_task1", 3)
+        );
+        assert_eq!(
+            Err(Refusal::AlreadyAccepted(7)),
+            store.check("day1_task2", 8)
+        );
+        assert_eq!(Ok(()), store.check("day2_task1", 8));
+    }
+}
@@ -0,0 +1,124 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    str::FromStr,
+};
+
+/// Command line arguments split into positionals, `--name value` options and switches.
+#[derive(Debug, Default)]
+pub struct Args {
+    positional: Vec<String>,
+    options: HashMap<String, String>,
+    switches: HashSet<String>,
+    verbosity: u8,
+}
+
+impl Args {
+    /// Parses raw arguments, names listed in `switches` never take a value.
+    pub fn parse(
+        raw: impl IntoIterator<Item = String>,
+        switches: &[&str],
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut args = Args::default();
+        let mut raw = raw.into_iter();
+
+        while let Some(arg) = raw.next() {
+            if let Some(vs) = arg.strip_prefix('-').filter(|vs| !vs.is_empty()) {
+                if vs.chars().all(|c| c == 'v') {
+                    let count = u8::try_from(vs.len()).unwrap_or(u8::MAX);
+                    args.verbosity = args.verbosity.saturating_add(count);
+                    continue;
+                }
+            }
+
+            let Some(name) = arg.strip_prefix("--") else {
+                args.positional.push(arg);
+                continue;
+            };
+
+            if let Some((name, value)) = name.split_once('=') {
+                args.options.insert(name.to_string(), value.to_string());
+            } else if switches.contains(&name) {
+                args.switches.insert(name.to_string());
+            } else {
+                let value = raw
+                    .next()
+                    .ok_or_else(|| format!("missing value for --{}", name))?;
+                args.options.insert(name.to_string(), value);
+            }
+        }
+
+        Ok(args)
+    }
+
+    pub fn positional(&self) -> &[String] {
+        &self.positional
+    }
+
+    pub fn option(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+
+    pub fn option_or<T>(&self, name: &str, default: T) -> Result<T, Box<dyn Error>>
+    where
+        T: FromStr,
+        T::Err: Error + 'static,
+    {
+        match self.option(name) {
+            Some(raw) => Ok(raw.parse()?),
+            None => Ok(default),
+        }
+    }
+
+    pub fn switch(&self, name: &str) -> bool {
+        self.switches.contains(name)
+    }
+
+    /// Number of `v`s passed as `-v`, `-vv`...
+    pub fn verbosity(&self) -> u8 {
+        self.verbosity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(raw: &[&str]) -> Args {
+        Args::parse(raw.iter().map(|a| a.to_string()), &["tests"]).unwrap()
+    }
+
+    #[test]
+    fn parse_test() {
+        let args = parse(&[
+            "day1_task1",
+            "--tests",
+            "-vv",
+            "--format",
+            "json",
+            "--iterations=5",
+        ]);
+
+        assert_eq!(["day1_task1".to_string()], args.positional());
+        assert_eq!(Some("json"), args.option("format"));
+        assert_eq!(5, args.option_or("iterations", 1).unwrap());
+        assert_eq!(7, args.option_or("missing", 7).unwrap());
+        assert!(args.switch("tests"));
+        assert!(!args.switch("format"));
+        assert_eq!(2, args.verbosity());
+    }
+
+    #[test]
+    fn parse_verbosity_saturates_test() {
+        let vs = |count| format!("-{}", "v".repeat(count));
+        let args = Args::parse([vs(200), vs(100)], &[]).unwrap();
+        assert_eq!(u8::MAX, args.verbosity());
+        let args = Args::parse([vs(300)], &[]).unwrap();
+        assert_eq!(u8::MAX, args.verbosity());
+    }
+
+    #[test]
+    fn parse_missing_value_test() {
+        assert!(Args::parse(["--format".to_string()], &[]).is_err());
+    }
+}
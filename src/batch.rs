@@ -0,0 +1,288 @@
+use std::{
+    error::Error,
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    answers::AnswersStore,
+    args::Args,
+    lint,
+    runner::{find_task, run_task},
+    task_registry::{day_of, Task, TaskRegistry},
+};
+
+const DEFAULT_INPUTS_DIR: &str = "inputs";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Correct,
+    Wrong,
+    /// The user has no accepted answer recorded for the task.
+    Unchecked,
+    Failed,
+    MissingInput,
+}
+
+impl Status {
+    fn label(self) -> &'static str {
+        match self {
+            Status::Correct => "ok",
+            Status::Wrong => "WRONG",
+            Status::Unchecked => "unchecked",
+            Status::Failed => "FAILED",
+            Status::MissingInput => "no input",
+        }
+    }
+
+    fn is_failure(self) -> bool {
+        matches!(self, Status::Wrong | Status::Failed)
+    }
+}
+
+/// Answer of one task on one user's input next to the answer that user recorded.
+#[derive(Debug)]
+struct BatchRow {
+    user: String,
+    task_id: String,
+    answer: Option<Result<i64, String>>,
+    expected: Option<i64>,
+    millis: f64,
+}
+
+impl BatchRow {
+    fn status(&self) -> Status {
+        match (&self.answer, self.expected) {
+            (None, _) => Status::MissingInput,
+            (Some(Err(_)), _) => Status::Failed,
+            (Some(Ok(_)), None) => Status::Unchecked,
+            (Some(Ok(answer)), Some(expected)) if *answer == expected => Status::Correct,
+            (Some(Ok(_)), Some(_)) => Status::Wrong,
+        }
+    }
+}
+
+/// Every subdirectory of `dir` is one user, sorted by name.
+fn users(dir: &Path) -> Result<Vec<(String, PathBuf)>, Box<dyn Error>> {
+    let mut users = Vec::new();
+    for entry in fs::read_dir(dir).map_err(|err| format!("{}: {}", dir.display(), err))? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            users.push((
+                entry.file_name().to_string_lossy().into_owned(),
+                entry.path(),
+            ));
+        }
+    }
+    users.sort();
+    Ok(users)
+}
+
+/// Runs each task on `<dir>/<user>/dayN.txt` and checks it against `<dir>/<user>/answers.txt`.
+fn batch_rows(
+    registry: &TaskRegistry,
+    tasks: &[&Task],
+    dir: &Path,
+) -> Result<Vec<BatchRow>, Box<dyn Error>> {
+    let mut rows = Vec::new();
+
+    for (user, user_dir) in users(dir)? {
+        let store = AnswersStore::load(user_dir.join("answers.txt"))?;
+
+        for task in tasks {
+            let input_path = user_dir.join(format!("day{}.txt", day_of(&task.id)?));
+            let mut row = BatchRow {
+                user: user.clone(),
+                task_id: task.id.clone(),
+                answer: None,
+                expected: store.accepted(&task.id),
+                millis: 0.0,
+            };
+
+            if input_path.is_file() {
+                let input_path = input_path.to_string_lossy();
+                lint::warn_about_input(registry, task, &input_path);
+                // Solvers unwrap on malformed input, which must not end the whole batch.
+                let report = panic::catch_unwind(AssertUnwindSafe(|| {
+                    run_task(task, task.default_implementation(), &input_path, 1)
+                }));
+                match report {
+                    Ok(report) => {
+                        row.answer = Some(report.answer);
+                        row.millis = report.timings.mean.as_secs_f64() * 1000.0;
+                    }
+                    Err(_) => row.answer = Some(Err("solver panicked".to_string())),
+                }
+            }
+            rows.push(row);
+        }
+    }
+    Ok(rows)
+}
+
+fn render_rows(rows: &[BatchRow]) -> Vec<String> {
+    let show = |value: Option<i64>| value.map_or("-".to_string(), |v| v.to_string());
+
+    let cells: Vec<[String; 6]> = rows
+        .iter()
+        .map(|row| {
+            let answer = match &row.answer {
+                Some(Ok(answer)) => answer.to_string(),
+                Some(Err(err)) => format!("error: {}", err),
+                None => "-".to_string(),
+            };
+            [
+                row.user.clone(),
+                row.task_id.clone(),
+                answer,
+                show(row.expected),
+                row.status().label().to_string(),
+                format!("{:.3}ms", row.millis),
+            ]
+        })
+        .collect();
+
+    let header = ["user", "task", "answer", "expected", "status", "time"].map(String::from);
+    let mut widths = header.clone().map(|h| h.len());
+    for row in &cells {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    std::iter::once(&header)
+        .chain(&cells)
+        .map(|row| {
+            let padded: Vec<_> = row
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect();
+            padded.join("  ").trim_end().to_string()
+        })
+        .collect()
+}
+
+/// `batch [task_id...] [--dir DIR]` - runs tasks on every user's input in `DIR/<user>/dayN.txt`
+/// (`inputs` by default) and checks the answers against `DIR/<user>/answers.txt`.
+pub fn run(registry: &TaskRegistry, args: &Args) -> Result<(), Box<dyn Error>> {
+    let tasks: Vec<&Task> = if args.positional().is_empty() {
+        registry.tasks().iter().collect()
+    } else {
+        args.positional()
+            .iter()
+            .map(|task_id| find_task(registry, task_id))
+            .collect::<Result<_, _>>()?
+    };
+    let dir = args.option("dir").unwrap_or(DEFAULT_INPUTS_DIR);
+
+    let rows = batch_rows(registry, &tasks, Path::new(dir))?;
+    for line in render_rows(&rows) {
+        println!("{}", line);
+    }
+
+    let failures = rows.iter().filter(|r| r.status().is_failure()).count();
+    if failures > 0 {
+        return Err(format!("{} wrong or failed answer(s)", failures).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    fn line_count(path: &str) -> Result<i64, Box<dyn Error>> {
+        Ok(fs::read_to_string(path)?.lines().count() as i64)
+    }
+
+    #[test]
+    fn batch_rows_test() {
+        let dir = env::temp_dir().join(format!("aoc2024_batch_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        for (user, input, answers) in [
+            ("alice", Some("a\nb\n"), "day1_task1 2 accepted\n"),
+            ("bob", Some("a\nb\nc\n"), "day1_task1 2 accepted\n"),
+            ("carol", Some("a\n"), ""),
+            ("dave", None, ""),
+        ] {
+            fs::create_dir_all(dir.join(user)).unwrap();
+            fs::write(dir.join(user).join("answers.txt"), answers).unwrap();
+            if let Some(input) = input {
+                fs::write(dir.join(user).join("day1.txt"), input).unwrap();
+            }
+        }
+
+        let registry = TaskRegistry::new(&[("day1_task1", line_count, "data/day1.txt")]);
+        let tasks: Vec<_> = registry.tasks().iter().collect();
+        let rows = batch_rows(&registry, &tasks, &dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let statuses: Vec<_> = rows.iter().map(|r| (r.user.as_str(), r.status())).collect();
+        assert_eq!(
+            vec![
+                ("alice", Status::Correct),
+                ("bob", Status::Wrong),
+                ("carol", Status::Unchecked),
+                ("dave", Status::MissingInput),
+            ],
+            statuses
+        );
+        assert_eq!(Some(Ok(3)), rows[1].answer);
+        assert_eq!(Some(2), rows[1].expected);
+    }
+
+    fn sum_lines(path: &str) -> Result<i64, Box<dyn Error>> {
+        Ok(fs::read_to_string(path)?
+            .lines()
+            .map(|line| line.parse::<i64>().unwrap())
+            .sum())
+    }
+
+    #[test]
+    fn batch_rows_survives_panic_test() {
+        let dir = env::temp_dir().join(format!("aoc2024_batch_panic_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        for (user, input) in [("alice", "not a number\n"), ("bob", "1\n2\n")] {
+            fs::create_dir_all(dir.join(user)).unwrap();
+            fs::write(
+                dir.join(user).join("answers.txt"),
+                "day1_task1 3 accepted\n",
+            )
+            .unwrap();
+            fs::write(dir.join(user).join("day1.txt"), input).unwrap();
+        }
+
+        let registry = TaskRegistry::new(&[("day1_task1", sum_lines, "data/day1.txt")]);
+        let tasks: Vec<_> = registry.tasks().iter().collect();
+        let rows = batch_rows(&registry, &tasks, &dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(Some(Err("solver panicked".to_string())), rows[0].answer);
+        assert_eq!(Status::Failed, rows[0].status());
+        assert_eq!(Some(Ok(3)), rows[1].answer);
+        assert_eq!(Status::Correct, rows[1].status());
+    }
+
+    #[test]
+    fn render_rows_test() {
+        let rows = vec![BatchRow {
+            user: "alice".to_string(),
+            task_id: "day6_task2".to_string(),
+            answer: Some(Ok(1434)),
+            expected: Some(1434),
+            millis: 1.5,
+        }];
+
+        assert_eq!(
+            vec![
+                "user   task        answer  expected  status  time",
+                "alice  day6_task2  1434    1434      ok      1.500ms",
+            ],
+            render_rows(&rows)
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use std::error::Error;
+
+use crate::{
+    args::Args,
+    runner::find_task,
+    task_registry::{Task, TaskRegistry},
+};
+
+/// Answers of every implementation of a task on a single input.
+#[derive(Debug)]
+struct CrossCheck {
+    task_id: String,
+    input_path: String,
+    answers: Vec<(String, Result<i64, String>)>,
+}
+
+impl CrossCheck {
+    fn agrees(&self) -> bool {
+        self.answers.windows(2).all(|w| w[0].1 == w[1].1)
+    }
+}
+
+fn crosscheck_task(task: &Task, input_path: &str) -> CrossCheck {
+    let answers = task
+        .implementations()
+        .iter()
+        .map(|implementation| {
+            let answer = (implementation.func)(input_path).map_err(|err| err.to_string());
+            (implementation.name.clone(), answer)
+        })
+        .collect();
+
+    CrossCheck {
+        task_id: task.id.clone(),
+        input_path: input_path.to_string(),
+        answers,
+    }
+}
+
+/// `crosscheck [task_id...] [--input PATH]` - runs all implementations of each task on the
+/// same input and reports where they disagree. Checks every task with alternatives by default.
+pub fn run(registry: &TaskRegistry, args: &Args) -> Result<(), Box<dyn Error>> {
+    let tasks: Vec<&Task> = if args.positional().is_empty() {
+        registry
+            .tasks()
+            .iter()
+            .filter(|t| t.implementations().len() > 1)
+            .collect()
+    } else {
+        args.positional()
+            .iter()
+            .map(|task_id| find_task(registry, task_id))
+            .collect::<Result<_, _>>()?
+    };
+
+    let mut disagreements = 0;
+
+    for task in tasks {
+        let input_path = args.option("input").unwrap_or(&task.input_path);
+        let check = crosscheck_task(task, input_path);
+
+        let answers: Vec<_> = check
+            .answers
+            .iter()
+            .map(|(name, answer)| match answer {
+                Ok(answer) => format!("{}={}", name, answer),
+                Err(err) => format!("{}=error({})", name, err),
+            })
+            .collect();
+
+        let status = if check.agrees() {
+            "OK"
+        } else {
+            disagreements += 1;
+            "MISMATCH"
+        };
+
+        println!(
+            "{} on {}: {} {}",
+            check.task_id,
+            check.input_path,
+            answers.join(" "),
+            status
+        );
+    }
+
+    if disagreements > 0 {
+        return Err(format!("{} task(s) with disagreeing implementations", disagreements).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive(_: &str) -> Result<i64, Box<dyn Error>> {
+        Ok(1)
+    }
+
+    fn fast(_: &str) -> Result<i64, Box<dyn Error>> {
+        Ok(1)
+    }
+
+    fn broken(_: &str) -> Result<i64, Box<dyn Error>> {
+        Ok(2)
+    }
+
+    #[test]
+    fn crosscheck_task_test() {
+        let registry = TaskRegistry::new(&[
+            ("agreeing", naive, "data/x.txt"),
+            ("disagreeing", naive, "data/x.txt"),
+        ])
+        .with_implementations(&[("agreeing", "fast", fast), ("disagreeing", "fast", broken)]);
+
+        let check = crosscheck_task(registry.get("agreeing").unwrap(), "data/x.txt");
+        assert!(check.agrees());
+        assert_eq!(2, check.answers.len());
+
+        let check = crosscheck_task(registry.get("disagreeing").unwrap(), "data/x.txt");
+        assert!(!check.agrees());
+        assert_eq!(("fast".to_string(), Ok(2)), check.answers[1]);
+    }
+}
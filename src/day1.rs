@@ -1,6 +1,23 @@
+mod external;
+pub mod metrics;
+
 use std::collections::HashMap;
 
-use crate::file_utils::read_lines_from_file;
+use crate::{
+    file_utils::read_lines_from_file,
+    lint::{InputSpec, Layout, LinePattern},
+    trace,
+};
+
+pub const INPUT_SPEC: InputSpec = InputSpec {
+    layout: Layout::Lines(LinePattern {
+        description: "two numbers",
+        regex: r"^\d+ +\d+$",
+    }),
+    allowed_chars: None,
+    exact_counts: &[],
+    min_real_bytes: 4096,
+};
 
 fn i64_pair_from_line(line: &str) -> Result<(i64, i64), Box<dyn std::error::Error>> {
     let mut pairs = line.split_whitespace();
@@ -15,45 +32,145 @@ fn read_number_pairs(filepath: &str) -> Result<Vec<(i64, i64)>, Box<dyn std::err
         .collect()
 }
 
-fn task1_run(input_path: &str) -> Result<i64, Box<dyn std::error::Error>> {
+/// Sum of differences between the lists once both are sorted.
+pub fn task1_run(input_path: &str) -> Result<i64, Box<dyn std::error::Error>> {
     let pairs = read_number_pairs(input_path)?;
+    let (left, right): (Vec<_>, Vec<_>) = pairs.into_iter().unzip();
+
+    metrics::Columns::from_columns(vec![left, right]).distance(0, 1, metrics::Distance::Absolute)
+}
 
-    let (mut left, mut right): (Vec<_>, Vec<_>) = pairs.into_iter().unzip();
-    left.sort_unstable();
-    right.sort_unstable();
+/// How the right column gets counted for the similarity score.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CountStrategy {
+    /// One slot per value between the smallest and largest, for bounded ids.
+    CountingArray,
+    /// Sort both columns and walk them together.
+    SortedMerge,
+    HashMap,
+}
+
+/// Widest value range given a counting array, 8MiB of counts.
+const MAX_COUNTING_RANGE: i64 = 1 << 20;
+
+/// Slots per counted value worth allocating and scanning before a hash map is cheaper.
+const MAX_COUNTING_SLOTS_PER_VALUE: u64 = 16;
 
-    Ok(left
-        .iter()
-        .zip(right.iter())
-        .map(|(a, b)| (a - b).abs())
-        .sum())
+/// Below this many values the hash map beats sorting, above it the map stops fitting in cache.
+const MIN_SORTED_MERGE_LEN: usize = 1 << 12;
+
+/// Span of the values, `None` for no values.
+fn value_range(values: &[i64]) -> Option<u64> {
+    let (min, max) = values.iter().min().zip(values.iter().max())?;
+    Some(max.abs_diff(*min))
 }
 
-fn task2_run(input_path: &str) -> Result<i64, Box<dyn std::error::Error>> {
+/// Whether a counting array over `right` stays within `MAX_COUNTING_RANGE`.
+fn counting_array_fits(right: &[i64]) -> bool {
+    value_range(right).is_some_and(|range| range < MAX_COUNTING_RANGE as u64)
+}
+
+fn choose_strategy(right: &[i64]) -> CountStrategy {
+    let dense = value_range(right)
+        .is_some_and(|range| range <= MAX_COUNTING_SLOTS_PER_VALUE * right.len() as u64);
+    if dense && counting_array_fits(right) {
+        CountStrategy::CountingArray
+    } else if right.len() >= MIN_SORTED_MERGE_LEN {
+        CountStrategy::SortedMerge
+    } else {
+        CountStrategy::HashMap
+    }
+}
+
+/// Sum of each left value times how often it occurs on the right.
+fn similarity(left: &[i64], right: &[i64], strategy: CountStrategy) -> i64 {
+    match strategy {
+        CountStrategy::CountingArray => {
+            let (Some(min), Some(max)) = (right.iter().min(), right.iter().max()) else {
+                return 0;
+            };
+            let mut counts = vec![0; (max - min) as usize + 1];
+            for val in right {
+                counts[(val - min) as usize] += 1;
+            }
+            left.iter()
+                .filter(|val| (min..=max).contains(val))
+                .map(|val| val * counts[(val - min) as usize])
+                .sum()
+        }
+        CountStrategy::SortedMerge => {
+            let mut left = left.to_vec();
+            let mut right = right.to_vec();
+            left.sort_unstable();
+            right.sort_unstable();
+            metrics::value_counts(&left, &right)
+                .map(|(val, left_count, right_count)| val * left_count * right_count)
+                .sum()
+        }
+        CountStrategy::HashMap => {
+            let counter: HashMap<_, _> = right.iter().fold(HashMap::new(), |mut acc, val| {
+                *acc.entry(val).or_default() += 1;
+                acc
+            });
+
+            left.iter()
+                .map(|val| val * counter.get(&val).unwrap_or(&0))
+                .sum()
+        }
+    }
+}
+
+fn task2_with(
+    input_path: &str,
+    strategy: Option<CountStrategy>,
+) -> Result<i64, Box<dyn std::error::Error>> {
     let pairs = read_number_pairs(input_path)?;
     let (left, right): (Vec<_>, Vec<_>) = pairs.into_iter().unzip();
 
-    let counter: HashMap<_, _> = right.iter().fold(HashMap::new(), |mut acc, val| {
-        *acc.entry(val).or_default() += 1;
-        acc
-    });
+    let strategy = match strategy {
+        Some(CountStrategy::CountingArray) if !counting_array_fits(&right) => {
+            return Err("value range too wide for a counting array".into())
+        }
+        Some(strategy) => strategy,
+        None => choose_strategy(&right),
+    };
+    trace::debug!("similarity strategy {:?}", strategy);
+    Ok(similarity(&left, &right, strategy))
+}
+
+/// Picks the counting strategy from the range and size of the right column.
+pub fn task2_run(input_path: &str) -> Result<i64, Box<dyn std::error::Error>> {
+    task2_with(input_path, None)
+}
+
+pub fn task2_counting_array_run(input_path: &str) -> Result<i64, Box<dyn std::error::Error>> {
+    task2_with(input_path, Some(CountStrategy::CountingArray))
+}
+
+pub fn task2_sorted_merge_run(input_path: &str) -> Result<i64, Box<dyn std::error::Error>> {
+    task2_with(input_path, Some(CountStrategy::SortedMerge))
+}
 
-    Ok(left
-        .into_iter()
-        .map(|val| val * counter.get(&val).unwrap_or(&0))
-        .sum())
+pub fn task2_hash_map_run(input_path: &str) -> Result<i64, Box<dyn std::error::Error>> {
+    task2_with(input_path, Some(CountStrategy::HashMap))
 }
 
-pub fn task1() -> Result<i64, Box<dyn std::error::Error>> {
-    task1_run("data/day1.txt")
+/// Same answer as `task1_run` for inputs too big to hold in memory.
+pub fn task1_external_run(input_path: &str) -> Result<i64, Box<dyn std::error::Error>> {
+    Ok(external::distance_and_similarity(input_path)?.0)
 }
 
-pub fn task2() -> Result<i64, Box<dyn std::error::Error>> {
-    task2_run("data/day1.txt")
+pub fn task2_external_run(input_path: &str) -> Result<i64, Box<dyn std::error::Error>> {
+    Ok(external::distance_and_similarity(input_path)?.1)
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::{
+        prop::{check, vec_of},
+        rng::Rng,
+    };
+
     use super::*;
     #[test]
     fn task1_test_data() {
@@ -74,4 +191,66 @@ mod tests {
     fn task2() {
         assert_eq!(24931009, task2_run("data/day1.txt").unwrap())
     }
+
+    #[test]
+    fn distance_is_symmetric_prop() {
+        check(
+            "day1 distance is symmetric",
+            |rng| vec_of(rng, 0..20, |rng| (rng.range(-1000..1000), rng.range(-1000..1000))),
+            |pairs: &Vec<(i64, i64)>| {
+                let (left, right): (Vec<_>, Vec<_>) = pairs.iter().cloned().unzip();
+                let columns = metrics::Columns::from_columns(vec![left, right]);
+                columns.distance(0, 1, metrics::Distance::Absolute).unwrap()
+                    == columns.distance(1, 0, metrics::Distance::Absolute).unwrap()
+            },
+        );
+    }
+
+    #[test]
+    fn similarity_strategies_test() {
+        for path in ["data/day1_test.txt", "data/day1.txt"] {
+            let expected = task2_run(path).unwrap();
+            assert_eq!(expected, task2_counting_array_run(path).unwrap());
+            assert_eq!(expected, task2_sorted_merge_run(path).unwrap());
+            assert_eq!(expected, task2_hash_map_run(path).unwrap());
+        }
+    }
+
+    #[test]
+    fn choose_strategy_test() {
+        let dense: Vec<_> = (0..1000).map(|val| 10000 + val * 10).collect();
+        assert_eq!(CountStrategy::CountingArray, choose_strategy(&dense));
+        // Two values far apart would scan a large array to count two numbers.
+        assert_eq!(CountStrategy::HashMap, choose_strategy(&[0, 1 << 19]));
+        assert_eq!(CountStrategy::HashMap, choose_strategy(&[10000, 99999]));
+        assert_eq!(CountStrategy::HashMap, choose_strategy(&[0, 1 << 40]));
+        assert_eq!(CountStrategy::HashMap, choose_strategy(&[]));
+        assert_eq!(
+            CountStrategy::SortedMerge,
+            choose_strategy(&(0..1 << 12).map(|val| val << 30).collect::<Vec<_>>())
+        );
+        assert_eq!(
+            CountStrategy::CountingArray,
+            choose_strategy(&[i64::MIN, i64::MIN + 5])
+        );
+    }
+
+    #[test]
+    fn similarity_strategies_agree_prop() {
+        check(
+            "day1 similarity strategies agree",
+            |rng| {
+                let spread = *rng.choose(&[10, 1 << 30]);
+                let mut value = |rng: &mut Rng| rng.range(-spread..spread);
+                (vec_of(rng, 0..20, &mut value), vec_of(rng, 0..20, &mut value))
+            },
+            |(left, right): &(Vec<i64>, Vec<i64>)| {
+                let expected = similarity(left, right, CountStrategy::HashMap);
+                // Wide ranges would allocate a counting array far beyond memory.
+                let counting_ok = !counting_array_fits(right)
+                    || expected == similarity(left, right, CountStrategy::CountingArray);
+                counting_ok && expected == similarity(left, right, CountStrategy::SortedMerge)
+            },
+        );
+    }
 }
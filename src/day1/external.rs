@@ -0,0 +1,325 @@
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    env,
+    error::Error,
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Read, Write},
+    iter::Peekable,
+    path::PathBuf,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use crate::{file_utils::read_lines_from_file, trace};
+
+use super::i64_pair_from_line;
+
+/// Values per column held in memory before a sorted run is spilled, at most 32MiB per column.
+const CHUNK_LEN: usize = 1 << 22;
+
+static NEXT_SPILL_DIR: AtomicU64 = AtomicU64::new(0);
+
+/// Temporary directory for sorted runs, removed with everything in it on drop.
+struct SpillDir {
+    path: PathBuf,
+    runs: usize,
+}
+
+impl SpillDir {
+    fn new() -> io::Result<Self> {
+        let path = env::temp_dir().join(format!(
+            "aoc2024_day1_{}_{}",
+            std::process::id(),
+            NEXT_SPILL_DIR.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&path)?;
+        Ok(SpillDir { path, runs: 0 })
+    }
+
+    fn write_run(&mut self, values: &[i64]) -> io::Result<PathBuf> {
+        let path = self.path.join(format!("run{}.bin", self.runs));
+        self.runs += 1;
+
+        let mut writer = BufWriter::new(File::create(&path)?);
+        for value in values {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        writer.flush()?;
+        Ok(path)
+    }
+}
+
+impl Drop for SpillDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Values of one sorted run read back from disk.
+struct RunReader {
+    reader: BufReader<File>,
+}
+
+impl Iterator for RunReader {
+    type Item = io::Result<i64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut bytes = [0; 8];
+        match self.reader.read_exact(&mut bytes) {
+            Ok(()) => Some(Ok(i64::from_le_bytes(bytes))),
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => None,
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+/// One column: the unsorted tail in memory plus the sorted runs already on disk.
+struct Column {
+    /// Grows as values arrive, so small inputs never allocate the whole chunk.
+    chunk: Vec<i64>,
+    chunk_len: usize,
+    runs: Vec<PathBuf>,
+}
+
+impl Column {
+    fn new(chunk_len: usize) -> Self {
+        Column {
+            chunk: Vec::new(),
+            chunk_len,
+            runs: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, value: i64, spill: &mut SpillDir) -> io::Result<()> {
+        self.chunk.push(value);
+        if self.chunk.len() >= self.chunk_len {
+            self.chunk.sort_unstable();
+            self.runs.push(spill.write_run(&self.chunk)?);
+            self.chunk.clear();
+        }
+        Ok(())
+    }
+
+    /// Merges the runs and the in-memory tail into one ascending stream.
+    fn into_sorted(mut self) -> io::Result<MergedRuns> {
+        self.chunk.sort_unstable();
+        trace::debug!("merging {} runs from disk", self.runs.len());
+
+        let mut sources: Vec<Box<dyn Iterator<Item = io::Result<i64>>>> = Vec::new();
+        for path in self.runs {
+            sources.push(Box::new(RunReader {
+                reader: BufReader::new(File::open(path)?),
+            }));
+        }
+        sources.push(Box::new(self.chunk.into_iter().map(Ok)));
+
+        let mut merged = MergedRuns {
+            sources,
+            heap: BinaryHeap::new(),
+        };
+        for idx in 0..merged.sources.len() {
+            merged.refill(idx)?;
+        }
+        Ok(merged)
+    }
+}
+
+/// K-way merge of ascending sources through a min-heap of their current heads.
+struct MergedRuns {
+    sources: Vec<Box<dyn Iterator<Item = io::Result<i64>>>>,
+    heap: BinaryHeap<Reverse<(i64, usize)>>,
+}
+
+impl MergedRuns {
+    fn refill(&mut self, idx: usize) -> io::Result<()> {
+        if let Some(value) = self.sources[idx].next().transpose()? {
+            self.heap.push(Reverse((value, idx)));
+        }
+        Ok(())
+    }
+}
+
+impl Iterator for MergedRuns {
+    type Item = io::Result<i64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((value, idx)) = self.heap.pop()?;
+        Some(self.refill(idx).map(|()| value))
+    }
+}
+
+/// Takes the run of values equal to the next one, returning the value and how often it occurs.
+fn next_group(
+    values: &mut Peekable<impl Iterator<Item = io::Result<i64>>>,
+) -> io::Result<Option<(i64, i64)>> {
+    let Some(value) = values.next().transpose()? else {
+        return Ok(None);
+    };
+    let mut count = 1;
+    while let Some(Ok(next)) = values.peek() {
+        if *next != value {
+            break;
+        }
+        values.next();
+        count += 1;
+    }
+    Ok(Some((value, count)))
+}
+
+/// Distance and similarity of both sorted columns in a single merged pass over their
+/// distinct values. Pairing by rank gives the same distance as integrating the gap between
+/// how many values of each column lie below `x`, which only changes at those values.
+/// Similarity is the sum of `value * left_count * right_count` over values in both columns.
+/// Fails like `metrics::Columns` when either does not fit in an `i64`.
+fn merged_pass(
+    left: impl Iterator<Item = io::Result<i64>>,
+    right: impl Iterator<Item = io::Result<i64>>,
+) -> Result<(i64, i64), Box<dyn Error>> {
+    const DISTANCE_OVERFLOW: &str = "Absolute distance overflows an i64";
+    const SIMILARITY_OVERFLOW: &str = "weighted count overflows an i64";
+
+    let mut left = left.peekable();
+    let mut right = right.peekable();
+    let mut distance: i64 = 0;
+    let mut similarity: i64 = 0;
+
+    let mut left_group = next_group(&mut left)?;
+    let mut right_group = next_group(&mut right)?;
+    // Values seen so far on the left minus on the right, and the last value seen.
+    let mut gap: i64 = 0;
+    let mut previous = None;
+
+    loop {
+        let value = match (left_group, right_group) {
+            (None, None) => break,
+            (Some((l, _)), None) => l,
+            (None, Some((r, _))) => r,
+            (Some((l, _)), Some((r, _))) => l.min(r),
+        };
+        if let Some(previous) = previous {
+            distance = value
+                .checked_sub(previous)
+                .and_then(|width| gap.checked_abs()?.checked_mul(width))
+                .and_then(|area| distance.checked_add(area))
+                .ok_or(DISTANCE_OVERFLOW)?;
+        }
+        previous = Some(value);
+
+        let mut counts = (0, 0);
+        if let Some((_, count)) = left_group.filter(|(l, _)| *l == value) {
+            counts.0 = count;
+            gap = gap.checked_add(count).ok_or(DISTANCE_OVERFLOW)?;
+            left_group = next_group(&mut left)?;
+        }
+        if let Some((_, count)) = right_group.filter(|(r, _)| *r == value) {
+            counts.1 = count;
+            gap = gap.checked_sub(count).ok_or(DISTANCE_OVERFLOW)?;
+            right_group = next_group(&mut right)?;
+        }
+        similarity = value
+            .checked_mul(counts.0)
+            .and_then(|product| product.checked_mul(counts.1))
+            .and_then(|product| similarity.checked_add(product))
+            .ok_or(SIMILARITY_OVERFLOW)?;
+    }
+
+    Ok((distance, similarity))
+}
+
+fn distance_and_similarity_chunked(
+    input_path: &str,
+    chunk_len: usize,
+) -> Result<(i64, i64), Box<dyn Error>> {
+    let mut spill = SpillDir::new()?;
+    let mut left = Column::new(chunk_len);
+    let mut right = Column::new(chunk_len);
+
+    for line in read_lines_from_file(input_path) {
+        let (l, r) = i64_pair_from_line(&line?)?;
+        left.push(l, &mut spill)?;
+        right.push(r, &mut spill)?;
+    }
+
+    merged_pass(left.into_sorted()?, right.into_sorted()?)
+}
+
+/// Streams the input and sorts both columns externally, so memory stays bounded by the
+/// chunk size however many pairs there are.
+pub fn distance_and_similarity(input_path: &str) -> Result<(i64, i64), Box<dyn Error>> {
+    distance_and_similarity_chunked(input_path, CHUNK_LEN)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::gen::generate;
+
+    use super::*;
+
+    #[test]
+    fn merged_pass_test() {
+        let left = [1, 2, 3, 3, 3, 4].map(Ok);
+        let right = [3, 3, 3, 4, 5, 9].map(Ok);
+        // Pairs by rank 1-3, 2-3, 3-3, 3-4, 3-5, 4-9.
+
+        assert_eq!(
+            (11, 31),
+            merged_pass(left.into_iter(), right.into_iter()).unwrap()
+        );
+    }
+
+    #[test]
+    fn merged_pass_overflow_test() {
+        let overflow = |left: &[i64], right: &[i64]| {
+            merged_pass(left.iter().copied().map(Ok), right.iter().copied().map(Ok))
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            "Absolute distance overflows an i64",
+            overflow(&[i64::MIN], &[i64::MAX])
+        );
+        assert_eq!(
+            "Absolute distance overflows an i64",
+            overflow(&[0, 0, 0], &[i64::MAX / 2, i64::MAX / 2, i64::MAX / 2])
+        );
+        assert_eq!(
+            "weighted count overflows an i64",
+            overflow(&[i64::MAX, i64::MAX], &[i64::MAX])
+        );
+    }
+
+    #[test]
+    fn column_spills_and_merges_test() {
+        let mut spill = SpillDir::new().unwrap();
+        let mut column = Column::new(3);
+        for value in [5, -1, 7, 3, 3, 0, 9, -4] {
+            column.push(value, &mut spill).unwrap();
+        }
+        assert_eq!(2, column.runs.len());
+
+        let sorted: Vec<_> = column.into_sorted().unwrap().map(Result::unwrap).collect();
+        assert_eq!(vec![-4, -1, 0, 3, 3, 5, 7, 9], sorted);
+
+        let spill_path = spill.path.clone();
+        drop(spill);
+        assert!(!spill_path.exists());
+    }
+
+    #[test]
+    fn matches_in_memory_on_generated_input_test() {
+        let path =
+            env::temp_dir().join(format!("aoc2024_day1_external_{}.txt", std::process::id()));
+        fs::write(&path, generate(1, 5000, 7).unwrap()).unwrap();
+        let path = path.to_str().unwrap();
+
+        let expected = (
+            super::super::task1_run(path).unwrap(),
+            super::super::task2_run(path).unwrap(),
+        );
+        assert_eq!(expected, distance_and_similarity_chunked(path, 64).unwrap());
+        assert_eq!(expected, distance_and_similarity(path).unwrap());
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,273 @@
+use std::error::Error;
+
+use crate::file_utils::read_lines_from_file;
+
+/// How two columns are compared value by value once both are sorted.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Distance {
+    /// Sum of `|a - b|`, the day 1 part 1 answer.
+    Absolute,
+    /// Sum of `(a - b)^2`.
+    Squared,
+    /// Number of sorted positions holding different values.
+    Hamming,
+}
+
+/// How much two columns have in common, ignoring order.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Similarity {
+    /// Sum of each value in the first column times how often it occurs in the second, the
+    /// day 1 part 2 answer. Not symmetric.
+    WeightedCount,
+    /// Size of the multiset intersection over the size of the union.
+    Jaccard,
+}
+
+/// Lists of numbers side by side, one row per line separated by whitespace, like the day 1
+/// input but with any number of columns.
+#[derive(Debug)]
+pub struct Columns {
+    sorted: Vec<Vec<i64>>,
+}
+
+impl Columns {
+    /// Every row must have as many values as the first one.
+    pub fn from_rows<R: AsRef<[i64]>>(rows: &[R]) -> Result<Self, Box<dyn Error>> {
+        let width = rows.first().map_or(0, |row| row.as_ref().len());
+        let mut columns = vec![Vec::with_capacity(rows.len()); width];
+
+        for (idx, row) in rows.iter().enumerate() {
+            let row = row.as_ref();
+            if row.len() != width {
+                return Err(format!(
+                    "row {}: expected {} columns, found {}",
+                    idx + 1,
+                    width,
+                    row.len()
+                )
+                .into());
+            }
+            for (column, value) in columns.iter_mut().zip(row) {
+                column.push(*value);
+            }
+        }
+
+        Ok(Self::from_columns(columns))
+    }
+
+    /// Takes the columns as they are, they may differ in length.
+    pub fn from_columns(mut columns: Vec<Vec<i64>>) -> Self {
+        for column in columns.iter_mut() {
+            column.sort_unstable();
+        }
+        Columns { sorted: columns }
+    }
+
+    pub fn from_file(input_path: &str) -> Result<Self, Box<dyn Error>> {
+        let rows = read_lines_from_file(input_path)
+            .enumerate()
+            .map(|(idx, line)| {
+                line?
+                    .split_whitespace()
+                    .map(|value| {
+                        value
+                            .parse::<i64>()
+                            .map_err(|err| format!("line {}: {:?}: {}", idx + 1, value, err).into())
+                    })
+                    .collect::<Result<Vec<_>, Box<dyn Error>>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_rows(&rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.sorted.len()
+    }
+
+    /// Values of column `idx` in ascending order.
+    pub fn sorted(&self, idx: usize) -> Result<&[i64], Box<dyn Error>> {
+        self.sorted
+            .get(idx)
+            .map(Vec::as_slice)
+            .ok_or_else(|| format!("no column {}, there are {}", idx, self.width()).into())
+    }
+
+    /// Pairs values by rank, extra values in the longer column are ignored. Fails if the
+    /// result does not fit in an `i64`.
+    pub fn distance(&self, a: usize, b: usize, metric: Distance) -> Result<i64, Box<dyn Error>> {
+        let mut pairs = self.sorted(a)?.iter().zip(self.sorted(b)?);
+
+        let distance = match metric {
+            Distance::Absolute => pairs.try_fold(0i64, |sum, (x, y)| {
+                sum.checked_add(x.checked_sub(*y)?.checked_abs()?)
+            }),
+            Distance::Squared => pairs.try_fold(0i64, |sum, (x, y)| {
+                let diff = x.checked_sub(*y)?;
+                sum.checked_add(diff.checked_mul(diff)?)
+            }),
+            Distance::Hamming => Some(pairs.filter(|(x, y)| x != y).count() as i64),
+        };
+        distance.ok_or_else(|| format!("{:?} distance overflows an i64", metric).into())
+    }
+
+    pub fn similarity(
+        &self,
+        a: usize,
+        b: usize,
+        metric: Similarity,
+    ) -> Result<f64, Box<dyn Error>> {
+        Ok(match metric {
+            Similarity::WeightedCount => self.weighted_count(a, b)? as f64,
+            Similarity::Jaccard => {
+                let (mut shared, mut total) = (0, 0);
+                for (_, count_a, count_b) in value_counts(self.sorted(a)?, self.sorted(b)?) {
+                    shared += count_a.min(count_b);
+                    total += count_a.max(count_b);
+                }
+                match total {
+                    0 => 1.0,
+                    total => shared as f64 / total as f64,
+                }
+            }
+        })
+    }
+
+    /// Exact integer form of `Similarity::WeightedCount`, fails if it does not fit in an `i64`.
+    pub fn weighted_count(&self, a: usize, b: usize) -> Result<i64, Box<dyn Error>> {
+        value_counts(self.sorted(a)?, self.sorted(b)?)
+            .try_fold(0i64, |sum, (value, count_a, count_b)| {
+                sum.checked_add(value.checked_mul(count_a)?.checked_mul(count_b)?)
+            })
+            .ok_or_else(|| "weighted count overflows an i64".into())
+    }
+
+    /// `metric` between every pair of columns, `matrix[a][b]`.
+    pub fn distance_matrix(&self, metric: Distance) -> Result<Vec<Vec<i64>>, Box<dyn Error>> {
+        (0..self.width())
+            .map(|a| {
+                (0..self.width())
+                    .map(|b| self.distance(a, b, metric))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Every distinct value of two sorted slices with how often it occurs in each.
+pub(super) fn value_counts<'a>(
+    mut a: &'a [i64],
+    mut b: &'a [i64],
+) -> impl Iterator<Item = (i64, i64, i64)> + 'a {
+    fn take_group(values: &mut &[i64], value: i64) -> i64 {
+        let count = values.iter().take_while(|v| **v == value).count();
+        *values = &values[count..];
+        count as i64
+    }
+
+    std::iter::from_fn(move || {
+        let value = match (a.first(), b.first()) {
+            (None, None) => return None,
+            (Some(x), None) => *x,
+            (None, Some(y)) => *y,
+            (Some(x), Some(y)) => *x.min(y),
+        };
+        Some((value, take_group(&mut a, value), take_group(&mut b, value)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Columns {
+        Columns::from_rows(&[
+            [3, 4, 3],
+            [4, 3, 4],
+            [2, 5, 2],
+            [1, 3, 1],
+            [3, 9, 3],
+            [3, 3, 3],
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn distance_test() {
+        let columns = sample();
+        assert_eq!(11, columns.distance(0, 1, Distance::Absolute).unwrap());
+        assert_eq!(35, columns.distance(0, 1, Distance::Squared).unwrap());
+        assert_eq!(5, columns.distance(0, 1, Distance::Hamming).unwrap());
+        assert_eq!(
+            vec![vec![0, 11, 0], vec![11, 0, 11], vec![0, 11, 0]],
+            columns.distance_matrix(Distance::Absolute).unwrap()
+        );
+    }
+
+    #[test]
+    fn similarity_test() {
+        let columns = sample();
+        assert_eq!(31, columns.weighted_count(0, 1).unwrap());
+        assert_eq!(
+            31.0,
+            columns.similarity(0, 1, Similarity::WeightedCount).unwrap()
+        );
+        // {1, 2, 3, 3, 3, 4} and {3, 3, 3, 4, 5, 9} share {3, 3, 3, 4} out of 8.
+        assert_eq!(0.5, columns.similarity(0, 1, Similarity::Jaccard).unwrap());
+        assert_eq!(1.0, columns.similarity(0, 2, Similarity::Jaccard).unwrap());
+    }
+
+    #[test]
+    fn from_file_test() {
+        let columns = Columns::from_file("data/day1.txt").unwrap();
+        assert_eq!(2, columns.width());
+        assert_eq!(2066446, columns.distance(0, 1, Distance::Absolute).unwrap());
+        assert_eq!(24931009, columns.weighted_count(0, 1).unwrap());
+    }
+
+    #[test]
+    fn errors_test() {
+        assert_eq!(
+            "row 2: expected 3 columns, found 2",
+            Columns::from_rows(&[vec![1, 2, 3], vec![4, 5]])
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            "no column 3, there are 3",
+            sample()
+                .distance(0, 3, Distance::Absolute)
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn overflow_test() {
+        let columns = Columns::from_columns(vec![vec![i64::MIN, 0], vec![i64::MAX, 0]]);
+        assert_eq!(
+            "Absolute distance overflows an i64",
+            columns
+                .distance(0, 1, Distance::Absolute)
+                .unwrap_err()
+                .to_string()
+        );
+        let columns = Columns::from_columns(vec![vec![0, 0], vec![1 << 32, 1]]);
+        assert_eq!(
+            (1 << 32) + 1,
+            columns.distance(0, 1, Distance::Absolute).unwrap()
+        );
+        assert_eq!(
+            "Squared distance overflows an i64",
+            columns
+                .distance(0, 1, Distance::Squared)
+                .unwrap_err()
+                .to_string()
+        );
+        let columns = Columns::from_columns(vec![vec![i64::MAX], vec![i64::MAX, i64::MAX]]);
+        assert_eq!(i64::MAX, columns.weighted_count(0, 0).unwrap());
+        assert_eq!(
+            "weighted count overflows an i64",
+            columns.weighted_count(0, 1).unwrap_err().to_string()
+        );
+    }
+}
@@ -1,40 +1,188 @@
-use crate::file_utils::read_lines_from_file;
+use std::{cmp::Ordering, fmt};
 
-fn is_monotonic(seq: &[i64]) -> bool {
-    let mut increasing = true;
-    let mut decreasing = true;
+use crate::{
+    file_utils::read_lines_from_file,
+    lint::{InputSpec, Layout, LinePattern},
+};
 
-    for i in 1..seq.len() {
-        let prev_idx = i - 1;
-        if seq[i] > seq[prev_idx] {
-            decreasing = false;
-        } else if seq[i] < seq[prev_idx] {
-            increasing = false;
+pub const INPUT_SPEC: InputSpec = InputSpec {
+    layout: Layout::Lines(LinePattern {
+        description: "numbers separated by spaces",
+        regex: r"^\d+( \d+)*$",
+    }),
+    allowed_chars: None,
+    exact_counts: &[],
+    min_real_bytes: 4096,
+};
+
+/// Which way the levels of a report may go.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Direction {
+    Increasing,
+    Decreasing,
+    /// Either way, as long as every step goes the same way.
+    Either,
+}
+
+/// What makes a report safe.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReportPolicy {
+    /// Smallest and largest allowed change between neighbouring levels.
+    pub min_step: i64,
+    pub max_step: i64,
+    pub direction: Direction,
+    /// Whether neighbouring levels may be equal, such steps are exempt from the other rules.
+    pub allow_plateaus: bool,
+}
+
+/// The rules of the puzzle.
+pub const PUZZLE_POLICY: ReportPolicy = ReportPolicy {
+    min_step: 1,
+    max_step: 3,
+    direction: Direction::Either,
+    allow_plateaus: false,
+};
+
+#[derive(Debug, PartialEq)]
+pub enum Rule {
+    Plateau,
+    StepTooSmall,
+    StepTooLarge,
+    WrongDirection,
+    DirectionChanged,
+}
+
+/// The first pair of neighbouring levels breaking the policy, `index` is the first of them.
+#[derive(Debug, PartialEq)]
+pub struct Violation {
+    pub index: usize,
+    pub pair: (i64, i64),
+    pub rule: Rule,
+}
+
+impl ReportPolicy {
+    pub fn check(&self, seq: &[i64]) -> Result<(), Violation> {
+        let mut direction = match self.direction {
+            Direction::Increasing => 1,
+            Direction::Decreasing => -1,
+            Direction::Either => 0,
+        };
+
+        for (index, pair) in seq.windows(2).enumerate() {
+            let violation = |rule| Violation {
+                index,
+                pair: (pair[0], pair[1]),
+                rule,
+            };
+            // A change too big for an i64 is above any maximum step.
+            let Some((diff, step)) = pair[1]
+                .checked_sub(pair[0])
+                .and_then(|diff| Some((diff, diff.checked_abs()?)))
+            else {
+                return Err(violation(Rule::StepTooLarge));
+            };
+
+            if diff == 0 {
+                if !self.allow_plateaus {
+                    return Err(violation(Rule::Plateau));
+                }
+                continue;
+            }
+            if step < self.min_step {
+                return Err(violation(Rule::StepTooSmall));
+            }
+            if step > self.max_step {
+                return Err(violation(Rule::StepTooLarge));
+            }
+            if diff.signum() * direction < 0 {
+                return Err(violation(match self.direction {
+                    Direction::Either => Rule::DirectionChanged,
+                    _ => Rule::WrongDirection,
+                }));
+            }
+            direction = diff.signum();
         }
 
-        if !increasing && !decreasing {
+        Ok(())
+    }
+
+    /// Whether `prev` to `next` is a safe step in `direction`, 1 for increasing and -1 for
+    /// decreasing.
+    fn is_safe_step(&self, prev: i64, next: i64, direction: i64) -> bool {
+        let Some(diff) = next
+            .checked_sub(prev)
+            .and_then(|diff| diff.checked_mul(direction))
+        else {
             return false;
-        }
+        };
+        (diff == 0 && self.allow_plateaus)
+            || (diff > 0 && (self.min_step..=self.max_step).contains(&diff))
     }
 
-    true
+    /// Parses `min=N`, `max=N`, `increasing`, `decreasing` and `plateaus`, starting from the
+    /// puzzle rules. Steps must satisfy `0 <= min <= max`.
+    pub fn from_args(args: &[&str]) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut policy = PUZZLE_POLICY;
+        for arg in args {
+            match arg.split_once('=') {
+                Some(("min", step)) => policy.min_step = step.parse()?,
+                Some(("max", step)) => policy.max_step = step.parse()?,
+                _ => match *arg {
+                    "increasing" => policy.direction = Direction::Increasing,
+                    "decreasing" => policy.direction = Direction::Decreasing,
+                    "plateaus" => policy.allow_plateaus = true,
+                    _ => return Err(format!("unknown policy option {}", arg).into()),
+                },
+            }
+        }
+
+        if policy.min_step < 0 {
+            return Err(format!("minimum step {} is negative", policy.min_step).into());
+        }
+        if policy.min_step > policy.max_step {
+            return Err(format!(
+                "minimum step {} is above the maximum {}",
+                policy.min_step, policy.max_step
+            )
+            .into());
+        }
+        Ok(policy)
+    }
 }
 
-// Slowly - diff between each neighbours must be between 1 & 3.
-fn is_slowly_increasing(seq: &[i64]) -> bool {
-    for i in 1..seq.len() {
-        let prev = seq[i - 1];
-        let next = seq[i];
-        let diff = (prev - next).abs();
-        if diff < 1 || diff > 3 {
-            return false;
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (prev, next) = self.pair;
+        let change = match next.cmp(&prev) {
+            Ordering::Greater => "increases",
+            Ordering::Less => "decreases",
+            Ordering::Equal => "stays level",
+        };
+        write!(
+            f,
+            "levels {} and {} ({} -> {}) ",
+            self.index + 1,
+            self.index + 2,
+            prev,
+            next
+        )?;
+        match self.rule {
+            Rule::Plateau => write!(f, "stay level"),
+            Rule::StepTooSmall | Rule::StepTooLarge => {
+                write!(f, "{} by {}, ", change, (next - prev).abs())?;
+                match self.rule {
+                    Rule::StepTooSmall => write!(f, "below the minimum step"),
+                    _ => write!(f, "above the maximum step"),
+                }
+            }
+            Rule::WrongDirection => write!(f, "{}, against the allowed direction", change),
+            Rule::DirectionChanged => write!(f, "{} after going the other way", change),
         }
     }
-    true
 }
 
 fn is_safe(seq: &[i64]) -> bool {
-    is_monotonic(seq) & is_slowly_increasing(seq)
+    PUZZLE_POLICY.check(seq).is_ok()
 }
 
 fn is_safe_v1(seq: &[i64]) -> bool {
@@ -61,50 +209,327 @@ fn is_safe_v2(seq: &[i64]) -> bool {
     return false;
 }
 
-fn i64_vec_from_line(line: String) -> Vec<i64> {
-    return line
-        .split_whitespace()
-        .map(|raw| raw.parse().unwrap())
-        .collect();
+/// Checks the report against `policy` as if the level at `skipped` was not there.
+fn is_safe_without(policy: &ReportPolicy, seq: &[i64], skipped: usize) -> bool {
+    let mut levels = seq
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| *idx != skipped)
+        .map(|(_, level)| *level);
+
+    let Some(mut prev) = levels.next() else {
+        return true;
+    };
+    let mut direction = match policy.direction {
+        Direction::Increasing => 1,
+        Direction::Decreasing => -1,
+        Direction::Either => 0,
+    };
+
+    for next in levels {
+        if direction == 0 {
+            direction = (next - prev).signum();
+        }
+        if !policy.is_safe_step(prev, next, direction) {
+            return false;
+        }
+        prev = next;
+    }
+
+    true
+}
+
+/// `is_safe_v2` without copying the report for each removal, still O(n²).
+fn is_safe_v2_no_alloc(seq: &[i64]) -> bool {
+    is_safe_v1(seq) || (0..seq.len()).any(|skipped| is_safe_without(&PUZZLE_POLICY, seq, skipped))
+}
+
+/// Fewest levels to remove so the rest is safe in `direction`, as the removed indices, or
+/// `None` if that takes more than `k`. `cost[i]` is the fewest removals keeping level `i` as
+/// the last one so far. Only the `k + 1` levels before it can precede it within the budget,
+/// so this is O(n·k).
+fn removals_in_direction(
+    policy: &ReportPolicy,
+    seq: &[i64],
+    k: usize,
+    direction: i64,
+) -> Option<Vec<usize>> {
+    let mut cost = vec![usize::MAX; seq.len()];
+    let mut previous_kept = vec![None; seq.len()];
+
+    for i in 0..seq.len() {
+        if i <= k {
+            cost[i] = i;
+        }
+        for j in i.saturating_sub(k + 1)..i {
+            let through_j = cost[j].saturating_add(i - j - 1);
+            if through_j < cost[i] && policy.is_safe_step(seq[j], seq[i], direction) {
+                cost[i] = through_j;
+                previous_kept[i] = Some(j);
+            }
+        }
+    }
+
+    let (last, total) = (seq.len().saturating_sub(k + 1)..seq.len())
+        .map(|i| (i, cost[i].saturating_add(seq.len() - 1 - i)))
+        .min_by_key(|(_, total)| *total)?;
+    if total > k {
+        return None;
+    }
+
+    let mut kept = vec![false; seq.len()];
+    let mut current = Some(last);
+    while let Some(i) = current {
+        kept[i] = true;
+        current = previous_kept[i];
+    }
+    Some((0..seq.len()).filter(|i| !kept[*i]).collect())
+}
+
+/// The Problem Dampener tolerating up to `k` bad levels: the fewest indices to remove for
+/// the report to be safe under `policy`, or `None` if more than `k` are needed. `k = 1` with
+/// the puzzle policy is part 2.
+pub fn dampened(policy: &ReportPolicy, seq: &[i64], k: usize) -> Option<Vec<usize>> {
+    if seq.is_empty() {
+        return Some(Vec::new());
+    }
+    let increasing = match policy.direction {
+        Direction::Decreasing => None,
+        _ => removals_in_direction(policy, seq, k, 1),
+    };
+    let decreasing = match policy.direction {
+        Direction::Increasing => None,
+        _ => removals_in_direction(policy, seq, k, -1),
+    };
+
+    match (increasing, decreasing) {
+        (Some(inc), Some(dec)) if dec.len() < inc.len() => Some(dec),
+        (None, dec) => dec,
+        (inc, _) => inc,
+    }
+}
+
+fn is_safe_dampened(seq: &[i64]) -> bool {
+    dampened(&PUZZLE_POLICY, seq, 1).is_some()
+}
+
+/// A blank line is an empty report, which is safe.
+fn i64_vec_from_line(line: &str) -> Result<Vec<i64>, String> {
+    line.split_whitespace()
+        .map(|raw| {
+            raw.parse()
+                .map_err(|err| format!("{:?} is not a level: {}", raw, err))
+        })
+        .collect()
+}
+
+/// What to do with lines that are not a report.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Malformed {
+    /// Stop at the first one with its line number.
+    Fail,
+    /// Leave them out, only counting them.
+    Skip,
+    /// Leave them out and keep their errors to report at the end.
+    Collect,
+}
+
+impl Malformed {
+    pub fn from_arg(arg: &str) -> Option<Self> {
+        match arg {
+            "fail" => Some(Malformed::Fail),
+            "skip" => Some(Malformed::Skip),
+            "collect" => Some(Malformed::Collect),
+            _ => None,
+        }
+    }
+}
+
+/// Lines of an input that were left out as not being a report.
+#[derive(Default)]
+struct MalformedLines {
+    count: usize,
+    errors: Vec<String>,
+}
+
+/// Passes each report of an input with its line number to `visit` as it is read.
+fn read_reports(
+    input_path: &str,
+    on_malformed: Malformed,
+    mut visit: impl FnMut(usize, &[i64]),
+) -> Result<MalformedLines, Box<dyn std::error::Error>> {
+    let mut malformed = MalformedLines::default();
+
+    for (idx, line) in read_lines_from_file(input_path).enumerate() {
+        match i64_vec_from_line(&line?) {
+            Ok(levels) => visit(idx + 1, &levels),
+            Err(err) => {
+                let err = format!("line {}: {}", idx + 1, err);
+                match on_malformed {
+                    Malformed::Fail => return Err(err.into()),
+                    Malformed::Skip => {}
+                    Malformed::Collect => malformed.errors.push(err),
+                }
+                malformed.count += 1;
+            }
+        }
+    }
+    Ok(malformed)
 }
 
 fn task_run(
     input_path: &str,
     predicate: fn(&[i64]) -> bool,
 ) -> Result<i64, Box<dyn std::error::Error>> {
-    let lines = read_lines_from_file(input_path);
-
-    Ok(lines
-        .map(|l| {
-            let line = l.unwrap();
-            let nums = i64_vec_from_line(line);
-            if predicate(nums.as_slice()) {
-                1
-            } else {
-                0
+    let mut count = 0;
+    read_reports(input_path, Malformed::Fail, |_, levels| {
+        if predicate(levels) {
+            count += 1;
+        }
+    })?;
+    Ok(count)
+}
+
+/// Every unsafe report under `policy` with the first rule it breaks.
+pub fn explain(
+    input_path: &str,
+    policy: &ReportPolicy,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mut explanations = Vec::new();
+    let mut reports = 0;
+    read_reports(input_path, Malformed::Fail, |line, levels| {
+        reports += 1;
+        if let Err(violation) = policy.check(levels) {
+            let levels: Vec<_> = levels.iter().map(i64::to_string).collect();
+            explanations.push(format!(
+                "line {}: {}: {}",
+                line,
+                levels.join(" "),
+                violation
+            ));
+        }
+    })?;
+
+    explanations.push(format!(
+        "{} of {} reports unsafe",
+        explanations.len(),
+        reports
+    ));
+    Ok(explanations.join("\n"))
+}
+
+/// How the reports of an input fare under a policy, with and without the dampener.
+#[derive(Debug, Default, PartialEq)]
+pub struct ReportStats {
+    pub safe: usize,
+    /// Unsafe as they are but safe with one level removed.
+    pub dampened_safe: usize,
+    pub unsafe_reports: usize,
+    pub malformed: usize,
+    /// Errors of the malformed lines when collected.
+    pub errors: Vec<String>,
+}
+
+impl fmt::Display for ReportStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "safe: {}\ndampened safe: {}\nunsafe: {}\nmalformed: {}",
+            self.safe, self.dampened_safe, self.unsafe_reports, self.malformed
+        )?;
+        for err in &self.errors {
+            write!(f, "\n  {}", err)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn stats(
+    input_path: &str,
+    policy: &ReportPolicy,
+    on_malformed: Malformed,
+) -> Result<ReportStats, Box<dyn std::error::Error>> {
+    let mut stats = ReportStats::default();
+    let malformed = read_reports(input_path, on_malformed, |_, levels| {
+        if policy.check(levels).is_ok() {
+            stats.safe += 1;
+        } else if dampened(policy, levels, 1).is_some() {
+            stats.dampened_safe += 1;
+        } else {
+            stats.unsafe_reports += 1;
+        }
+    })?;
+
+    stats.malformed = malformed.count;
+    stats.errors = malformed.errors;
+    Ok(stats)
+}
+
+/// What the dampener makes of every report unsafe under `policy` when it may remove up to
+/// `k` levels, with the levels it removes.
+pub fn explain_dampened(
+    input_path: &str,
+    policy: &ReportPolicy,
+    k: usize,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mut explanations = Vec::new();
+    let (mut reports, mut safe) = (0, 0);
+    read_reports(input_path, Malformed::Fail, |line, levels| {
+        reports += 1;
+        let explanation = match dampened(policy, levels, k) {
+            Some(removed) if removed.is_empty() => {
+                safe += 1;
+                return;
             }
-        })
-        .sum())
+            Some(removed) => {
+                safe += 1;
+                let removed: Vec<_> = removed
+                    .iter()
+                    .map(|idx| format!("level {} ({})", idx + 1, levels[*idx]))
+                    .collect();
+                format!("remove {}", removed.join(", "))
+            }
+            None => format!("unsafe even with up to {} removed", k),
+        };
+        let levels: Vec<_> = levels.iter().map(i64::to_string).collect();
+        explanations.push(format!(
+            "line {}: {}: {}",
+            line,
+            levels.join(" "),
+            explanation
+        ));
+    })?;
+
+    explanations.push(format!(
+        "{} of {} reports safe with up to {} removed",
+        safe, reports, k
+    ));
+    Ok(explanations.join("\n"))
 }
 
-fn task1_run(input_path: &str) -> Result<i64, Box<dyn std::error::Error>> {
+pub fn task1_run(input_path: &str) -> Result<i64, Box<dyn std::error::Error>> {
     task_run(input_path, is_safe_v1)
 }
 
-fn task2_run(input_path: &str) -> Result<i64, Box<dyn std::error::Error>> {
-    task_run(input_path, is_safe_v2)
+pub fn task2_run(input_path: &str) -> Result<i64, Box<dyn std::error::Error>> {
+    task_run(input_path, is_safe_dampened)
 }
 
-pub fn task1() -> Result<i64, Box<dyn std::error::Error>> {
-    task1_run("data/day2.txt")
+pub fn task2_naive_run(input_path: &str) -> Result<i64, Box<dyn std::error::Error>> {
+    task_run(input_path, is_safe_v2)
 }
 
-pub fn task2() -> Result<i64, Box<dyn std::error::Error>> {
-    task2_run("data/day2_test.txt")
+pub fn task2_no_alloc_run(input_path: &str) -> Result<i64, Box<dyn std::error::Error>> {
+    task_run(input_path, is_safe_v2_no_alloc)
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::{
+        file_utils::with_memory_input,
+        prop::{check, vec_of},
+    };
+
     use super::*;
 
     #[test]
@@ -126,4 +551,310 @@ mod tests {
     fn task2() {
         assert_eq!(589, task2_run("data/day2.txt").unwrap())
     }
+
+    #[test]
+    fn task2_no_alloc_test() {
+        assert_eq!(4, task2_no_alloc_run("data/day2_test.txt").unwrap());
+        assert_eq!(589, task2_no_alloc_run("data/day2.txt").unwrap());
+    }
+
+    #[test]
+    fn dampener_matches_brute_force_prop() {
+        check(
+            "day2 no-alloc dampener agrees with removing each level",
+            |rng| {
+                let mut level = rng.range(1..50);
+                vec_of(rng, 0..9, |rng| {
+                    level += rng.range(-4..5);
+                    level
+                })
+            },
+            |seq: &Vec<i64>| is_safe_v2_no_alloc(seq) == is_safe_v2(seq),
+        );
+    }
+
+    #[test]
+    fn task2_naive_test() {
+        assert_eq!(4, task2_naive_run("data/day2_test.txt").unwrap());
+        assert_eq!(589, task2_naive_run("data/day2.txt").unwrap());
+    }
+
+    #[test]
+    fn dampened_test() {
+        assert_eq!(Some(vec![]), dampened(&PUZZLE_POLICY, &[7, 6, 4, 2, 1], 1));
+        assert_eq!(None, dampened(&PUZZLE_POLICY, &[1, 2, 7, 8, 9], 1));
+        assert_eq!(Some(vec![2]), dampened(&PUZZLE_POLICY, &[1, 3, 2, 4, 5], 1));
+        assert_eq!(Some(vec![3]), dampened(&PUZZLE_POLICY, &[8, 6, 4, 4, 1], 1));
+        assert_eq!(None, dampened(&PUZZLE_POLICY, &[1, 3, 2, 4, 5, 4, 6], 1));
+        assert_eq!(
+            Some(vec![2, 5]),
+            dampened(&PUZZLE_POLICY, &[1, 3, 2, 4, 5, 4, 6], 2)
+        );
+        assert_eq!(Some(vec![0]), dampened(&PUZZLE_POLICY, &[9, 1, 2, 3], 1));
+        assert_eq!(Some(vec![]), dampened(&PUZZLE_POLICY, &[], 0));
+    }
+
+    /// Smallest set of at most `k` indices whose removal leaves a safe report.
+    fn dampened_exhaustive(seq: &[i64], k: usize) -> Option<usize> {
+        (0..1u32 << seq.len())
+            .filter(|mask| mask.count_ones() as usize <= k)
+            .filter(|mask| {
+                let rest: Vec<_> = (0..seq.len())
+                    .filter(|i| mask & (1 << i) == 0)
+                    .map(|i| seq[i])
+                    .collect();
+                is_safe(&rest)
+            })
+            .map(|mask| mask.count_ones() as usize)
+            .min()
+    }
+
+    #[test]
+    fn dampened_matches_exhaustive_prop() {
+        check(
+            "day2 dp dampener removes as few levels as trying every subset",
+            |rng| {
+                let k = rng.range(0..4);
+                let mut level = rng.range(1..50);
+                let seq = vec_of(rng, 0..10, |rng| {
+                    level += rng.range(-4..5);
+                    level
+                });
+                (k, seq)
+            },
+            |(k, seq): &(i64, Vec<i64>)| {
+                let k = *k as usize;
+                let Some(removed) = dampened(&PUZZLE_POLICY, seq, k) else {
+                    return dampened_exhaustive(seq, k).is_none();
+                };
+                let rest: Vec<_> = (0..seq.len())
+                    .filter(|i| !removed.contains(i))
+                    .map(|i| seq[i])
+                    .collect();
+                is_safe(&rest) && Some(removed.len()) == dampened_exhaustive(seq, k)
+            },
+        );
+    }
+
+    #[test]
+    fn policy_check_test() {
+        let violation = |index, pair, rule| Err(Violation { index, pair, rule });
+        assert_eq!(Ok(()), PUZZLE_POLICY.check(&[7, 6, 4, 2, 1]));
+        assert_eq!(
+            violation(1, (2, 7), Rule::StepTooLarge),
+            PUZZLE_POLICY.check(&[1, 2, 7, 8, 9])
+        );
+        assert_eq!(
+            violation(1, (3, 2), Rule::DirectionChanged),
+            PUZZLE_POLICY.check(&[1, 3, 2, 4, 5])
+        );
+        assert_eq!(
+            violation(2, (4, 4), Rule::Plateau),
+            PUZZLE_POLICY.check(&[8, 6, 4, 4, 1])
+        );
+
+        let policy =
+            ReportPolicy::from_args(&["min=2", "max=5", "increasing", "plateaus"]).unwrap();
+        assert_eq!(Ok(()), policy.check(&[1, 3, 3, 8]));
+        assert_eq!(
+            violation(0, (1, 2), Rule::StepTooSmall),
+            policy.check(&[1, 2])
+        );
+        assert_eq!(
+            violation(1, (3, 1), Rule::WrongDirection),
+            policy.check(&[1, 3, 1])
+        );
+        assert!(ReportPolicy::from_args(&["sideways"]).is_err());
+        assert_eq!(
+            "minimum step 5 is above the maximum 2",
+            ReportPolicy::from_args(&["min=5", "max=2"])
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            "minimum step 4 is above the maximum 3",
+            ReportPolicy::from_args(&["min=4"]).unwrap_err().to_string()
+        );
+        assert_eq!(
+            "minimum step -1 is negative",
+            ReportPolicy::from_args(&["min=-1", "max=-1"])
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn explain_test() {
+        assert_eq!(
+            "line 2: 1 2 7 8 9: levels 2 and 3 (2 -> 7) increases by 5, above the maximum step\n\
+             line 3: 9 7 6 2 1: levels 3 and 4 (6 -> 2) decreases by 4, above the maximum step\n\
+             line 4: 1 3 2 4 5: levels 2 and 3 (3 -> 2) decreases after going the other way\n\
+             line 5: 8 6 4 4 1: levels 3 and 4 (4 -> 4) stay level\n\
+             4 of 6 reports unsafe",
+            explain("data/day2_test.txt", &PUZZLE_POLICY).unwrap()
+        );
+    }
+
+    #[test]
+    fn explain_dampened_test() {
+        assert_eq!(
+            "line 2: 1 2 7 8 9: unsafe even with up to 1 removed\n\
+             line 3: 9 7 6 2 1: unsafe even with up to 1 removed\n\
+             line 4: 1 3 2 4 5: remove level 3 (2)\n\
+             line 5: 8 6 4 4 1: remove level 4 (4)\n\
+             4 of 6 reports safe with up to 1 removed",
+            explain_dampened("data/day2_test.txt", &PUZZLE_POLICY, 1).unwrap()
+        );
+        assert!(explain_dampened("data/day2_test.txt", &PUZZLE_POLICY, 2)
+            .unwrap()
+            .contains("line 2: 1 2 7 8 9: remove level 1 (1), level 2 (2)\n"));
+    }
+
+    #[test]
+    fn extreme_levels_test() {
+        assert_eq!(
+            Err(Violation {
+                index: 0,
+                pair: (i64::MIN, i64::MAX),
+                rule: Rule::StepTooLarge
+            }),
+            PUZZLE_POLICY.check(&[i64::MIN, i64::MAX])
+        );
+        assert_eq!(
+            Err(Violation {
+                index: 0,
+                pair: (0, i64::MIN),
+                rule: Rule::StepTooLarge
+            }),
+            PUZZLE_POLICY.check(&[0, i64::MIN])
+        );
+        assert!(!PUZZLE_POLICY.is_safe_step(i64::MAX, i64::MIN, 1));
+        assert_eq!(
+            Some(vec![1]),
+            dampened(&PUZZLE_POLICY, &[i64::MAX - 2, i64::MIN, i64::MAX], 1)
+        );
+        assert_eq!(None, dampened(&PUZZLE_POLICY, &[i64::MIN, i64::MAX], 0));
+    }
+
+    #[test]
+    fn dampened_policy_test() {
+        let policy = ReportPolicy::from_args(&["decreasing", "plateaus"]).unwrap();
+        assert_eq!(Some(vec![]), dampened(&policy, &[8, 6, 4, 4, 1], 1));
+        assert_eq!(Some(vec![0]), dampened(&policy, &[1, 6, 4, 4, 1], 1));
+        assert_eq!(None, dampened(&policy, &[1, 3, 2, 4, 5], 1));
+    }
+
+    const POLICY_ARGS: &[&str] = &[
+        "min=0",
+        "min=2",
+        "max=2",
+        "max=4",
+        "increasing",
+        "decreasing",
+        "plateaus",
+    ];
+
+    #[test]
+    fn policy_check_matches_dampener_prop() {
+        check(
+            "day2 policy check agrees with the dampener without removals",
+            |rng| {
+                let options = vec_of(rng, 0..4, |rng| rng.range(0..POLICY_ARGS.len() as i64));
+                let mut level = rng.range(1..50);
+                let seq = vec_of(rng, 0..9, |rng| {
+                    level += rng.range(-5..6);
+                    level
+                });
+                (options, seq)
+            },
+            |(options, seq): &(Vec<i64>, Vec<i64>)| {
+                let args: Vec<_> = options
+                    .iter()
+                    .map(|idx| POLICY_ARGS[*idx as usize])
+                    .collect();
+                let policy = ReportPolicy::from_args(&args).unwrap();
+                policy.check(seq).is_ok() == dampened(&policy, seq, 0).is_some()
+            },
+        );
+    }
+
+    #[test]
+    fn is_safe_without_matches_policy_check_prop() {
+        check(
+            "day2 is_safe_without agrees with checking the report minus one level",
+            |rng| {
+                let options = vec_of(rng, 0..4, |rng| rng.range(0..POLICY_ARGS.len() as i64));
+                let mut level = rng.range(1..50);
+                let seq = vec_of(rng, 1..9, |rng| {
+                    level += rng.range(-5..6);
+                    level
+                });
+                let skipped = rng.index(seq.len()) as i64;
+                ((options, skipped), seq)
+            },
+            |((options, skipped), seq): &((Vec<i64>, i64), Vec<i64>)| {
+                let skipped = *skipped as usize;
+                let args: Vec<_> = options
+                    .iter()
+                    .map(|idx| POLICY_ARGS[*idx as usize])
+                    .collect();
+                let policy = ReportPolicy::from_args(&args).unwrap();
+                let mut rest = seq.clone();
+                if skipped < rest.len() {
+                    rest.remove(skipped);
+                }
+                policy.check(&rest).is_ok() == is_safe_without(&policy, seq, skipped)
+            },
+        );
+    }
+
+    #[test]
+    fn malformed_lines_test() {
+        let content = "7 6 4 2 1\n1 2 x 8 9\n\n1 3 2 4 5\n1 2 7 8 9\n";
+        with_memory_input(content, |path| {
+            assert_eq!(
+                "line 2: \"x\" is not a level: invalid digit found in string",
+                task1_run(path).unwrap_err().to_string()
+            );
+            assert!(explain(path, &PUZZLE_POLICY).is_err());
+
+            // The blank line is an empty report, which is safe.
+            let expected = ReportStats {
+                safe: 2,
+                dampened_safe: 1,
+                unsafe_reports: 1,
+                malformed: 1,
+                errors: Vec::new(),
+            };
+            assert_eq!(
+                expected,
+                stats(path, &PUZZLE_POLICY, Malformed::Skip).unwrap()
+            );
+
+            let collected = stats(path, &PUZZLE_POLICY, Malformed::Collect).unwrap();
+            assert_eq!(
+                "safe: 2\n\
+                 dampened safe: 1\n\
+                 unsafe: 1\n\
+                 malformed: 1\n  \
+                 line 2: \"x\" is not a level: invalid digit found in string",
+                collected.to_string()
+            );
+        });
+        with_memory_input("7 6 4 2 1\n\n1 2 7 8 9\n", |path| {
+            assert_eq!(2, task1_run(path).unwrap());
+            assert_eq!(2, task2_run(path).unwrap());
+        });
+    }
+
+    #[test]
+    fn stats_test() {
+        let stats = stats("data/day2.txt", &PUZZLE_POLICY, Malformed::Fail).unwrap();
+        assert_eq!(549, stats.safe);
+        assert_eq!(589, stats.safe + stats.dampened_safe);
+        assert_eq!(
+            1000,
+            stats.safe + stats.dampened_safe + stats.unsafe_reports
+        );
+    }
 }
@@ -1,17 +1,48 @@
-use std::fs::read_to_string;
+mod scanner;
+pub mod vm;
+
+use std::{io, ops::Range, sync::OnceLock};
 
 use regex::Regex;
 
-#[derive(Debug, PartialEq)]
-enum Op {
+use crate::{
+    file_utils::{buf_reader, read_to_string},
+    lint::{InputSpec, Layout},
+};
+
+pub const INPUT_SPEC: InputSpec = InputSpec {
+    layout: Layout::Text,
+    allowed_chars: None,
+    exact_counts: &[],
+    min_real_bytes: 4096,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Op {
     Enable,
     Disable,
     Mul { left: i64, right: i64 },
 }
 
+/// An `Op` with the bytes of the input it was parsed from.
+#[derive(Debug, PartialEq)]
+pub struct SpannedOp {
+    pub op: Op,
+    pub span: Range<usize>,
+}
+
 fn find_ops_in_line(haystack: &str) -> Vec<Op> {
-    let re_op =
-        Regex::new(r"mul\((?P<left_op>\d{1,3}),(?P<right_op>\d{1,3})\)|(do(n't)?\(\))").unwrap();
+    find_spanned_ops(haystack)
+        .into_iter()
+        .map(|spanned| spanned.op)
+        .collect()
+}
+
+fn find_spanned_ops(haystack: &str) -> Vec<SpannedOp> {
+    static RE_OP: OnceLock<Regex> = OnceLock::new();
+    let re_op = RE_OP.get_or_init(|| {
+        Regex::new(r"mul\((?P<left_op>\d{1,3}),(?P<right_op>\d{1,3})\)|(do(n't)?\(\))").unwrap()
+    });
 
     let captures = re_op.captures_iter(&haystack);
 
@@ -19,7 +50,7 @@ fn find_ops_in_line(haystack: &str) -> Vec<Op> {
         .map(|c| {
             let full_match = c.get(0).unwrap();
 
-            match full_match.as_str() {
+            let op = match full_match.as_str() {
                 "don't()" => Op::Disable,
                 "do()" => Op::Enable,
                 _ => {
@@ -33,6 +64,10 @@ fn find_ops_in_line(haystack: &str) -> Vec<Op> {
 
                     Op::Mul { left, right }
                 }
+            };
+            SpannedOp {
+                op,
+                span: full_match.range(),
             }
         })
         .collect();
@@ -40,54 +75,172 @@ fn find_ops_in_line(haystack: &str) -> Vec<Op> {
     result
 }
 
-fn task1_run(input_path: &str) -> Result<i64, Box<dyn std::error::Error>> {
-    let data = read_to_string(input_path).unwrap();
-    let result = find_ops_in_line(&data)
+/// Both parts are the same machine with different instruction sets.
+fn run_machine(
+    input_path: &str,
+    instructions: &[vm::InstructionSpec],
+    accumulator: vm::Accumulator,
+) -> Result<i64, Box<dyn std::error::Error>> {
+    let data = read_to_string(input_path)?;
+    let state = vm::Machine::new(instructions)?
+        .with_accumulator(accumulator)
+        .run(&data)?;
+    i64::try_from(state.acc)
+        .map_err(|_| format!("result {} does not fit in an i64", state.acc).into())
+}
+
+pub fn task1_run(input_path: &str) -> Result<i64, Box<dyn std::error::Error>> {
+    run_machine(input_path, vm::PART1, vm::Accumulator::I64)
+}
+
+pub fn task2_run(input_path: &str) -> Result<i64, Box<dyn std::error::Error>> {
+    run_machine(input_path, vm::PART2, vm::Accumulator::I64)
+}
+
+/// Runs on the `i128` accumulator, so only the final result has to fit in an `i64`.
+pub fn task1_wide_run(input_path: &str) -> Result<i64, Box<dyn std::error::Error>> {
+    run_machine(input_path, vm::PART1, vm::Accumulator::I128)
+}
+
+pub fn task2_wide_run(input_path: &str) -> Result<i64, Box<dyn std::error::Error>> {
+    run_machine(input_path, vm::PART2, vm::Accumulator::I128)
+}
+
+/// Every instruction part `part` executes with the state after it.
+pub fn describe_trace(path: &str, part: u32) -> Result<String, Box<dyn std::error::Error>> {
+    let instructions = match part {
+        1 => vm::PART1,
+        2 => vm::PART2,
+        _ => return Err(format!("no part {}", part).into()),
+    };
+    let data = read_to_string(path)?;
+    let (state, steps) = vm::Machine::new(instructions)?.run_traced(&data)?;
+
+    let mut lines: Vec<_> = steps
         .iter()
-        .map(|v| match v {
-            Op::Mul { left, right } => left * right,
-            _ => 0,
+        .map(|step| {
+            let args: Vec<_> = step.args.iter().map(i64::to_string).collect();
+            format!(
+                "{}({}) -> {} acc {}",
+                step.instruction,
+                args.join(","),
+                if step.state.enabled {
+                    "enabled"
+                } else {
+                    "disabled"
+                },
+                step.state.acc
+            )
         })
-        .sum::<i64>();
-    Ok(result)
+        .collect();
+    lines.push(format!(
+        "{} instructions, result {}",
+        steps.len(),
+        state.acc
+    ));
+    Ok(lines.join("\n"))
 }
 
-fn task2_run(input_path: &str) -> Result<i64, Box<dyn std::error::Error>> {
-    let data = read_to_string(input_path).unwrap();
-    let ops = find_ops_in_line(&data);
+pub fn task1_ops_run(input_path: &str) -> Result<i64, Box<dyn std::error::Error>> {
+    let data = read_to_string(input_path)?;
+    evaluate(find_ops_in_line(&data).into_iter().map(Ok), false)
+}
 
-    let mut enabled = true;
-    let mut total = 0;
+pub fn task2_ops_run(input_path: &str) -> Result<i64, Box<dyn std::error::Error>> {
+    let data = read_to_string(input_path)?;
+    evaluate(find_ops_in_line(&data).into_iter().map(Ok), true)
+}
 
+/// Sums the enabled multiplications, `do()` and `don't()` only count with `toggles`.
+fn evaluate(
+    ops: impl Iterator<Item = io::Result<Op>>,
+    toggles: bool,
+) -> Result<i64, Box<dyn std::error::Error>> {
+    let mut enabled = true;
+    let mut total: i64 = 0;
     for op in ops {
-        match op {
-            Op::Disable => {
-                enabled = false;
-            }
-            Op::Enable => {
-                enabled = true;
-            }
-            Op::Mul { left, right } => {
-                if enabled {
-                    total += left * right
-                }
+        match op? {
+            Op::Enable => enabled = true,
+            Op::Disable => enabled = !toggles,
+            Op::Mul { left, right } if enabled => {
+                total = left
+                    .checked_mul(right)
+                    .and_then(|product| total.checked_add(product))
+                    .ok_or_else(|| {
+                        format!("mul({},{}) overflows the total of {}", left, right, total)
+                    })?;
             }
+            Op::Mul { .. } => {}
         }
     }
-
     Ok(total)
 }
 
-pub fn task1() -> Result<i64, Box<dyn std::error::Error>> {
-    task1_run("data/day3_test.txt")
+fn scan_ops(input_path: &str) -> io::Result<impl Iterator<Item = io::Result<Op>>> {
+    let ops = scanner::Scanner::new(buf_reader(input_path)?);
+    Ok(ops.map(|spanned| spanned.map(|spanned| spanned.op)))
+}
+
+/// Streams the input through the byte scanner instead of reading it whole.
+pub fn task1_scanner_run(input_path: &str) -> Result<i64, Box<dyn std::error::Error>> {
+    evaluate(scan_ops(input_path)?, false)
+}
+
+pub fn task2_scanner_run(input_path: &str) -> Result<i64, Box<dyn std::error::Error>> {
+    evaluate(scan_ops(input_path)?, true)
+}
+
+const MUL_STYLE: &str = "\x1b[32m";
+const TOGGLE_STYLE: &str = "\x1b[1;36m";
+const DISABLED_STYLE: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+/// The input with ANSI colours: `mul` green, `do()` and `don't()` cyan, and everything
+/// part 2 ignores after a `don't()` dimmed.
+pub fn highlight(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() * 2);
+    let mut enabled = true;
+    let mut cursor = 0;
+
+    fn push(out: &mut String, styles: &[&str], text: &str) {
+        if text.is_empty() || styles.iter().all(|style| style.is_empty()) {
+            out.push_str(text);
+            return;
+        }
+        out.extend(styles.iter().copied());
+        out.push_str(text);
+        out.push_str(RESET);
+    }
+
+    for SpannedOp { op, span } in find_spanned_ops(text) {
+        let dim = if enabled { "" } else { DISABLED_STYLE };
+        push(&mut out, &[dim], &text[cursor..span.start]);
+
+        match op {
+            Op::Mul { .. } => push(&mut out, &[dim, MUL_STYLE], &text[span.clone()]),
+            Op::Enable | Op::Disable => push(&mut out, &[TOGGLE_STYLE], &text[span.clone()]),
+        }
+        enabled = match op {
+            Op::Enable => true,
+            Op::Disable => false,
+            Op::Mul { .. } => enabled,
+        };
+        cursor = span.end;
+    }
+
+    let dim = if enabled { "" } else { DISABLED_STYLE };
+    push(&mut out, &[dim], &text[cursor..]);
+    out
 }
 
-pub fn task2() -> Result<i64, Box<dyn std::error::Error>> {
-    task2_run("data/day3_test.txt")
+pub fn describe_highlighted(path: &str) -> Result<String, Box<dyn std::error::Error>> {
+    Ok(highlight(&read_to_string(path)?))
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::file_utils::with_memory_input;
+
     use super::*;
 
     #[test]
@@ -154,4 +307,101 @@ mod tests {
     fn task2() {
         assert_eq!(89349241, task2_run("data/day3.txt").unwrap());
     }
+
+    #[test]
+    fn describe_trace_test() {
+        assert_eq!(
+            "mul(2,4) -> enabled acc 8\n\
+             don't() -> disabled acc 8\n\
+             mul(5,5) -> disabled acc 8\n\
+             mul(11,8) -> disabled acc 8\n\
+             do() -> enabled acc 8\n\
+             mul(8,5) -> enabled acc 48\n\
+             6 instructions, result 48",
+            describe_trace("data/day3_test2.txt", 2).unwrap()
+        );
+        assert!(describe_trace("data/day3_test2.txt", 3).is_err());
+    }
+
+    #[test]
+    fn ops_test() {
+        assert_eq!(159833790, task1_ops_run("data/day3.txt").unwrap());
+        assert_eq!(89349241, task2_ops_run("data/day3.txt").unwrap());
+    }
+
+    #[test]
+    fn wide_test() {
+        assert_eq!(159833790, task1_wide_run("data/day3.txt").unwrap());
+        assert_eq!(89349241, task2_wide_run("data/day3.txt").unwrap());
+    }
+
+    #[test]
+    fn run_machine_result_out_of_range_test() {
+        const WIDE_MUL: vm::InstructionSpec = vm::InstructionSpec {
+            arg_pattern: r"\d{1,18}",
+            ..vm::MUL
+        };
+        let text = "mul(999999999999,999999999)mul(999999999999,999999999)";
+        with_memory_input(text, |path| {
+            assert_eq!(
+                "result 1999999997998000000002 does not fit in an i64",
+                run_machine(path, &[WIDE_MUL], vm::Accumulator::I128)
+                    .unwrap_err()
+                    .to_string()
+            );
+            assert!(run_machine(path, &[WIDE_MUL], vm::Accumulator::I64).is_err());
+        });
+    }
+
+    #[test]
+    fn evaluate_overflow_test() {
+        let ops = [
+            Op::Mul {
+                left: i64::MAX / 2,
+                right: 2,
+            },
+            Op::Disable,
+            Op::Mul { left: 2, right: 1 },
+        ];
+        assert_eq!(
+            i64::MAX - 1,
+            evaluate(ops.iter().map(|op| Ok(op.clone())), true).unwrap()
+        );
+        assert_eq!(
+            format!("mul(2,1) overflows the total of {}", i64::MAX - 1),
+            evaluate(ops.into_iter().map(Ok), false)
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn spans_test() {
+        let text = "xmul(2,4)&don't()_mul(05,5)do()";
+        let spans: Vec<_> = find_spanned_ops(text)
+            .into_iter()
+            .map(|spanned| &text[spanned.span])
+            .collect();
+        assert_eq!(vec!["mul(2,4)", "don't()", "mul(05,5)", "do()"], spans);
+    }
+
+    #[test]
+    fn highlight_test() {
+        assert_eq!(
+            "x\x1b[32mmul(2,4)\x1b[0m&\
+             \x1b[1;36mdon't()\x1b[0m\
+             \x1b[2m_\x1b[0m\
+             \x1b[2m\x1b[32mmul(5,5)\x1b[0m\
+             \x1b[1;36mdo()\x1b[0m?",
+            highlight("xmul(2,4)&don't()_mul(5,5)do()?")
+        );
+    }
+
+    #[test]
+    fn scanner_test() {
+        assert_eq!(161, task1_scanner_run("data/day3_test.txt").unwrap());
+        assert_eq!(159833790, task1_scanner_run("data/day3.txt").unwrap());
+        assert_eq!(48, task2_scanner_run("data/day3_test2.txt").unwrap());
+        assert_eq!(89349241, task2_scanner_run("data/day3.txt").unwrap());
+    }
 }
@@ -0,0 +1,240 @@
+use std::io::{self, BufRead};
+
+use super::{Op, SpannedOp};
+
+const MUL: &[u8] = b"mul(";
+const DO: &[u8] = b"do()";
+const DONT: &[u8] = b"don't()";
+
+/// Where the scanner is within a possible instruction.
+#[derive(Clone, Copy, Debug)]
+enum State {
+    Start,
+    /// `matched` bytes of one of the keywords so far.
+    Keyword {
+        keyword: &'static [u8],
+        matched: usize,
+    },
+    Left {
+        digits: u32,
+        value: i64,
+    },
+    Right {
+        left: i64,
+        left_digits: u32,
+        digits: u32,
+        value: i64,
+    },
+}
+
+impl State {
+    /// Advances over `byte`, returning the instruction it completes and its length.
+    fn step(&mut self, byte: u8) -> Option<(Op, usize)> {
+        let (state, op) = match *self {
+            State::Start => (
+                match byte {
+                    b'm' => State::Keyword {
+                        keyword: MUL,
+                        matched: 1,
+                    },
+                    b'd' => State::Keyword {
+                        keyword: DO,
+                        matched: 1,
+                    },
+                    _ => State::Start,
+                },
+                None,
+            ),
+            State::Keyword { keyword, matched } if keyword[matched] == byte => {
+                if matched + 1 < keyword.len() {
+                    (
+                        State::Keyword {
+                            keyword,
+                            matched: matched + 1,
+                        },
+                        None,
+                    )
+                } else if keyword == MUL {
+                    (
+                        State::Left {
+                            digits: 0,
+                            value: 0,
+                        },
+                        None,
+                    )
+                } else if keyword == DO {
+                    (State::Start, Some((Op::Enable, DO.len())))
+                } else {
+                    (State::Start, Some((Op::Disable, DONT.len())))
+                }
+            }
+            State::Keyword {
+                keyword: DO,
+                matched: 2,
+            } if byte == b'n' => (
+                State::Keyword {
+                    keyword: DONT,
+                    matched: 3,
+                },
+                None,
+            ),
+            State::Left { digits, value } if digits < 3 && byte.is_ascii_digit() => (
+                State::Left {
+                    digits: digits + 1,
+                    value: value * 10 + i64::from(byte - b'0'),
+                },
+                None,
+            ),
+            State::Left { digits, value } if digits > 0 && byte == b',' => (
+                State::Right {
+                    left: value,
+                    left_digits: digits,
+                    digits: 0,
+                    value: 0,
+                },
+                None,
+            ),
+            State::Right {
+                left,
+                left_digits,
+                digits,
+                value,
+            } if digits < 3 && byte.is_ascii_digit() => (
+                State::Right {
+                    left,
+                    left_digits,
+                    digits: digits + 1,
+                    value: value * 10 + i64::from(byte - b'0'),
+                },
+                None,
+            ),
+            State::Right {
+                left,
+                left_digits,
+                digits,
+                value,
+            } if digits > 0 && byte == b')' => (
+                State::Start,
+                Some((
+                    Op::Mul { left, right: value },
+                    MUL.len() + (left_digits + digits) as usize + 2,
+                )),
+            ),
+            // No instruction contains an `m` or `d` after its first byte, so the byte that
+            // broke the match is the only place a new one can start.
+            _ => {
+                *self = State::Start;
+                return self.step(byte);
+            }
+        };
+
+        *self = state;
+        op
+    }
+}
+
+/// Finds the same instructions as `find_spanned_ops` one byte at a time, holding only the
+/// reader's buffer and the partial instruction in memory. The state survives refills, so
+/// instructions split between chunks are still found.
+pub struct Scanner<R> {
+    reader: R,
+    state: State,
+    /// Bytes consumed so far.
+    offset: usize,
+}
+
+impl<R: BufRead> Scanner<R> {
+    pub fn new(reader: R) -> Self {
+        Scanner {
+            reader,
+            state: State::Start,
+            offset: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Scanner<R> {
+    type Item = io::Result<SpannedOp>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let chunk = match self.reader.fill_buf() {
+                Ok([]) => return None,
+                Ok(chunk) => chunk,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Some(Err(err)),
+            };
+
+            let mut consumed = 0;
+            let mut found = None;
+            for &byte in chunk {
+                consumed += 1;
+                found = self.state.step(byte);
+                if found.is_some() {
+                    break;
+                }
+            }
+            self.reader.consume(consumed);
+            self.offset += consumed;
+
+            if let Some((op, len)) = found {
+                let span = self.offset - len..self.offset;
+                return Some(Ok(SpannedOp { op, span }));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+
+    use crate::{
+        day3::find_spanned_ops,
+        gen::generate,
+        prop::{check, vec_of},
+    };
+
+    use super::*;
+
+    /// Scans with the reader refilling every `chunk_len` bytes.
+    fn scan(text: &str, chunk_len: usize) -> Vec<SpannedOp> {
+        Scanner::new(BufReader::with_capacity(chunk_len, text.as_bytes()))
+            .collect::<io::Result<_>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn split_across_chunks_test() {
+        let text = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        for chunk_len in 1..=text.len() {
+            assert_eq!(
+                find_spanned_ops(text),
+                scan(text, chunk_len),
+                "{}",
+                chunk_len
+            );
+        }
+    }
+
+    #[test]
+    fn matches_regex_on_generated_input_test() {
+        let text = generate(3, 2000, 11).unwrap();
+        assert_eq!(find_spanned_ops(&text), scan(&text, 64));
+    }
+
+    #[test]
+    fn matches_regex_prop() {
+        const PIECES: &[&str] = &[
+            "mul(", "do", "don't", "()", "(", ")", ",", "1", "23", "456", "7890", "m", "d", "x",
+        ];
+        check(
+            "day3 scanner finds the same ops and spans as the regex",
+            |rng| vec_of(rng, 0..30, |rng| rng.range(0..PIECES.len() as i64)),
+            |pieces: &Vec<i64>| {
+                let text: String = pieces.iter().map(|idx| PIECES[*idx as usize]).collect();
+                find_spanned_ops(&text) == scan(&text, 3)
+            },
+        );
+    }
+}
@@ -0,0 +1,344 @@
+use std::{error::Error, fmt};
+
+use regex::Regex;
+
+use crate::trace;
+
+/// Range the accumulator and every intermediate result must stay within.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Accumulator {
+    #[default]
+    I64,
+    /// For instruction sets whose running total leaves the `i64` range on the way.
+    I128,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "arithmetic overflow")
+    }
+}
+
+impl Error for Overflow {}
+
+/// Everything an instruction can read and change.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct State {
+    pub enabled: bool,
+    pub acc: i128,
+    pub accumulator: Accumulator,
+}
+
+impl State {
+    pub fn new(accumulator: Accumulator) -> Self {
+        State {
+            enabled: true,
+            acc: 0,
+            accumulator,
+        }
+    }
+
+    /// `value` if the accumulator can hold it.
+    fn checked(&self, value: Option<i128>) -> Result<i128, Overflow> {
+        match (self.accumulator, value) {
+            (Accumulator::I64, Some(value)) if i64::try_from(value).is_ok() => Ok(value),
+            (Accumulator::I128, Some(value)) => Ok(value),
+            _ => Err(Overflow),
+        }
+    }
+
+    pub fn mul(&self, left: i64, right: i64) -> Result<i128, Overflow> {
+        self.checked(i128::from(left).checked_mul(i128::from(right)))
+    }
+
+    pub fn add(&mut self, value: i128) -> Result<(), Overflow> {
+        self.acc = self.checked(self.acc.checked_add(value))?;
+        Ok(())
+    }
+}
+
+/// An instruction written as `name(arg,...)` in the corrupted memory.
+pub struct InstructionSpec {
+    pub name: &'static str,
+    pub arity: usize,
+    /// Regex each argument must match in full, like `\d{1,3}`.
+    pub arg_pattern: &'static str,
+    /// Arithmetic goes through the checked `State` methods.
+    pub execute: fn(&mut State, &[i64]) -> Result<(), Overflow>,
+}
+
+pub const MUL: InstructionSpec = InstructionSpec {
+    name: "mul",
+    arity: 2,
+    arg_pattern: r"\d{1,3}",
+    execute: |state, args| {
+        if state.enabled {
+            state.add(state.mul(args[0], args[1])?)?;
+        }
+        Ok(())
+    },
+};
+
+pub const DO: InstructionSpec = InstructionSpec {
+    name: "do",
+    arity: 0,
+    arg_pattern: "",
+    execute: |state, _| {
+        state.enabled = true;
+        Ok(())
+    },
+};
+
+pub const DONT: InstructionSpec = InstructionSpec {
+    name: "don't",
+    arity: 0,
+    arg_pattern: "",
+    execute: |state, _| {
+        state.enabled = false;
+        Ok(())
+    },
+};
+
+/// Part 1 only knows `mul`, so `do()` and `don't()` are just more corruption.
+pub const PART1: &[InstructionSpec] = &[MUL];
+pub const PART2: &[InstructionSpec] = &[MUL, DO, DONT];
+
+/// One executed instruction and the state it left behind.
+#[derive(Debug, PartialEq)]
+pub struct Step {
+    pub instruction: &'static str,
+    pub args: Vec<i64>,
+    pub state: State,
+}
+
+/// Finds the instructions of its set in corrupted memory and executes them in order.
+pub struct Machine<'a> {
+    instructions: &'a [InstructionSpec],
+    regex: Regex,
+    /// Capture group of each instruction and of each of its arguments, in `instructions` order.
+    groups: Vec<(usize, Vec<usize>)>,
+    accumulator: Accumulator,
+}
+
+impl<'a> Machine<'a> {
+    /// Compiles one regex recognising every instruction of the set, earlier ones win ties.
+    pub fn new(instructions: &'a [InstructionSpec]) -> Result<Self, Box<dyn Error>> {
+        let mut alternatives = Vec::new();
+        for (idx, spec) in instructions.iter().enumerate() {
+            if instructions[..idx].iter().any(|s| s.name == spec.name) {
+                return Err(format!("instruction {} registered twice", spec.name).into());
+            }
+            let args: Vec<_> = (0..spec.arity)
+                .map(|arg| format!("(?P<i{}a{}>(?:{}))", idx, arg, spec.arg_pattern))
+                .collect();
+            alternatives.push(format!(
+                r"(?P<i{}>{}\({}\))",
+                idx,
+                regex::escape(spec.name),
+                args.join(",")
+            ));
+        }
+
+        let regex = Regex::new(&alternatives.join("|"))?;
+        let group = |name: String| {
+            regex
+                .capture_names()
+                .position(|group| group == Some(name.as_str()))
+                .ok_or_else(|| format!("no capture group {}", name))
+        };
+        let groups = instructions
+            .iter()
+            .enumerate()
+            .map(|(idx, spec)| {
+                let args = (0..spec.arity)
+                    .map(|arg| group(format!("i{}a{}", idx, arg)))
+                    .collect::<Result<_, _>>()?;
+                Ok((group(format!("i{}", idx))?, args))
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Machine {
+            instructions,
+            regex,
+            groups,
+            accumulator: Accumulator::default(),
+        })
+    }
+
+    pub fn with_accumulator(mut self, accumulator: Accumulator) -> Self {
+        self.accumulator = accumulator;
+        self
+    }
+
+    /// Every recognised instruction in `text` with its parsed arguments.
+    pub fn decode<'t>(
+        &'t self,
+        text: &'t str,
+    ) -> impl Iterator<Item = Result<(&'a InstructionSpec, Vec<i64>), Box<dyn Error>>> + 't {
+        self.regex.captures_iter(text).map(|captures| {
+            let (spec, (_, arg_groups)) = self
+                .instructions
+                .iter()
+                .zip(&self.groups)
+                .find(|(_, (group, _))| captures.get(*group).is_some())
+                .ok_or("match without an instruction")?;
+
+            let args = arg_groups
+                .iter()
+                .map(|group| {
+                    let raw = &captures[*group];
+                    raw.parse()
+                        .map_err(|err| format!("{}: bad argument {:?}: {}", spec.name, raw, err))
+                })
+                .collect::<Result<_, _>>()?;
+            Ok((spec, args))
+        })
+    }
+
+    fn execute(
+        &self,
+        text: &str,
+        mut steps: Option<&mut Vec<Step>>,
+    ) -> Result<State, Box<dyn Error>> {
+        let mut state = State::new(self.accumulator);
+        for decoded in self.decode(text) {
+            let (spec, args) = decoded?;
+            (spec.execute)(&mut state, &args).map_err(|err| {
+                format!(
+                    "{}{:?} with accumulator {}: {}",
+                    spec.name, args, state.acc, err
+                )
+            })?;
+            trace::trace!("{}({:?}) -> {:?}", spec.name, args, state);
+
+            if let Some(steps) = steps.as_mut() {
+                steps.push(Step {
+                    instruction: spec.name,
+                    args,
+                    state,
+                });
+            }
+        }
+        Ok(state)
+    }
+
+    pub fn run(&self, text: &str) -> Result<State, Box<dyn Error>> {
+        self.execute(text, None)
+    }
+
+    /// Like `run`, also returning every step.
+    pub fn run_traced(&self, text: &str) -> Result<(State, Vec<Step>), Box<dyn Error>> {
+        let mut steps = Vec::new();
+        let state = self.execute(text, Some(&mut steps))?;
+        Ok((state, steps))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str =
+        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    #[test]
+    fn parts_test() {
+        assert_eq!(161, Machine::new(PART1).unwrap().run(SAMPLE).unwrap().acc);
+        assert_eq!(48, Machine::new(PART2).unwrap().run(SAMPLE).unwrap().acc);
+    }
+
+    #[test]
+    fn run_traced_test() {
+        let (state, steps) = Machine::new(PART2).unwrap().run_traced(SAMPLE).unwrap();
+        let step = |instruction, args: &[i64], enabled, acc| Step {
+            instruction,
+            args: args.to_vec(),
+            state: State {
+                acc,
+                enabled,
+                ..State::new(Accumulator::I64)
+            },
+        };
+
+        assert_eq!(
+            vec![
+                step("mul", &[2, 4], true, 8),
+                step("don't", &[], false, 8),
+                step("mul", &[5, 5], false, 8),
+                step("mul", &[11, 8], false, 8),
+                step("do", &[], true, 8),
+                step("mul", &[8, 5], true, 48),
+            ],
+            steps
+        );
+        assert_eq!(steps.last().unwrap().state, state);
+    }
+
+    #[test]
+    fn custom_instructions_test() {
+        const ADD: InstructionSpec = InstructionSpec {
+            name: "add",
+            arity: 2,
+            arg_pattern: r"-?\d{1,3}",
+            execute: |state, args| state.add(i128::from(args[0]) + i128::from(args[1])),
+        };
+        // Enables only when its argument is odd.
+        const COND: InstructionSpec = InstructionSpec {
+            name: "cond",
+            arity: 1,
+            arg_pattern: r"\d",
+            execute: |state, args| {
+                state.enabled = args[0] % 2 == 1;
+                Ok(())
+            },
+        };
+        let instructions = [MUL, ADD, COND];
+        let machine = Machine::new(&instructions).unwrap();
+
+        let state = machine
+            .run("add(1,-3)cond(2)mul(4,4)cond(12)cond(7)mul(2,3)add(1)add(1000,1)")
+            .unwrap();
+        assert_eq!((true, 4), (state.enabled, state.acc));
+
+        assert!(Machine::new(&[MUL, DO, MUL]).is_err());
+    }
+
+    #[test]
+    fn overflow_test() {
+        const WIDE_MUL: InstructionSpec = InstructionSpec {
+            arg_pattern: r"\d{1,18}",
+            ..MUL
+        };
+        const SUB: InstructionSpec = InstructionSpec {
+            name: "sub",
+            arity: 1,
+            arg_pattern: r"\d{1,18}",
+            execute: |state, args| state.add(-i128::from(args[0])),
+        };
+        let instructions = [WIDE_MUL, SUB];
+        let text = "mul(999999999999,999999999)sub(999999999999000000)mul(1,1)";
+
+        let err = Machine::new(&instructions).unwrap().run(text).unwrap_err();
+        assert_eq!(
+            "mul[999999999999, 999999999] with accumulator 0: arithmetic overflow",
+            err.to_string()
+        );
+
+        let state = Machine::new(&instructions)
+            .unwrap()
+            .with_accumulator(Accumulator::I128)
+            .run(text)
+            .unwrap();
+        assert_eq!(998999998999001000002, state.acc);
+
+        let err = Machine::new(&instructions)
+            .unwrap()
+            .with_accumulator(Accumulator::I128)
+            .run(&"mul(999999999999999999,999999999999999999)".repeat(200))
+            .unwrap_err();
+        assert!(err.to_string().ends_with("arithmetic overflow"));
+    }
+}
@@ -1,6 +1,16 @@
 use std::error::Error;
 
-use crate::file_utils::read_lines_from_file_v2;
+use crate::{
+    file_utils::read_lines_from_file_v2,
+    lint::{InputSpec, Layout},
+};
+
+pub const INPUT_SPEC: InputSpec = InputSpec {
+    layout: Layout::Grid,
+    allowed_chars: Some("XMAS"),
+    exact_counts: &[],
+    min_real_bytes: 4096,
+};
 
 fn row_chars_to_i64(rows: &Vec<String>) -> Vec<Vec<i64>> {
     rows.iter()
@@ -154,22 +164,14 @@ fn task_run(path: &str, count_fn: SearchFn) -> Result<i64, Box<dyn Error>> {
     Ok(count)
 }
 
-fn task1_run(path: &str) -> Result<i64, Box<dyn Error>> {
+pub fn task1_run(path: &str) -> Result<i64, Box<dyn Error>> {
     task_run(path, count_xmas)
 }
 
-fn task2_run(path: &str) -> Result<i64, Box<dyn Error>> {
+pub fn task2_run(path: &str) -> Result<i64, Box<dyn Error>> {
     task_run(path, count_xmas_v2)
 }
 
-pub fn task1() -> Result<i64, Box<dyn Error>> {
-    task1_run("data/day4_test.txt")
-}
-
-pub fn task2() -> Result<i64, Box<dyn Error>> {
-    task2_run("data/day4_test.txt")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4,7 +4,27 @@ use std::{
     error::Error,
 };
 
-use crate::file_utils::read_lines_from_file_v2;
+use crate::{
+    file_utils::read_lines_from_file_v2,
+    lint::{InputSpec, Layout, LinePattern},
+    trace,
+};
+
+pub const INPUT_SPEC: InputSpec = InputSpec {
+    layout: Layout::Sections(&[
+        LinePattern {
+            description: "a rule like 47|53",
+            regex: r"^\d+\|\d+$",
+        },
+        LinePattern {
+            description: "comma separated pages",
+            regex: r"^\d+(,\d+)*$",
+        },
+    ]),
+    allowed_chars: None,
+    exact_counts: &[],
+    min_real_bytes: 4096,
+};
 
 #[derive(Debug, PartialEq)]
 struct OrderingRule {
@@ -120,6 +140,7 @@ fn filter_valid_pages_to_produce(input_data: InputData) -> impl Iterator<Item =
         if is_valid_page_to_produce(&page, &input_data.ordering_rules) {
             Some(page)
         } else {
+            trace::debug!("invalid update: {:?}", page.values);
             None
         }
     })
@@ -134,7 +155,7 @@ fn sum_middle_item_of_pages(pages: impl Iterator<Item = PageToProduce>) -> i64 {
         .sum()
 }
 
-fn task1_run(path: &str) -> Result<i64, Box<dyn Error>> {
+pub fn task1_run(path: &str) -> Result<i64, Box<dyn Error>> {
     let lines = read_lines_from_file_v2(path);
     let input_data = InputData::from_iter(lines)?;
     let valid_pages = filter_valid_pages_to_produce(input_data);
@@ -146,29 +167,59 @@ fn fix_invalid_pages(input_data: InputData) -> Vec<PageToProduce> {
         .pages
         .into_iter()
         .filter(|page| !is_valid_page_to_produce(page, &input_data.ordering_rules))
-        .map(|page| page.to_sorted(&input_data.ordering_rules))
+        .map(|page| {
+            let fixed = page.to_sorted(&input_data.ordering_rules);
+            trace::debug!("fixed update: {:?} -> {:?}", page.values, fixed.values);
+            fixed
+        })
         .collect()
 }
 
-fn task2_run(path: &str) -> Result<i64, Box<dyn Error>> {
+pub fn task2_run(path: &str) -> Result<i64, Box<dyn Error>> {
     let lines = read_lines_from_file_v2(path);
     let input_data = InputData::from_iter(lines)?;
     let invalid_pages_fixed = fix_invalid_pages(input_data);
     Ok(sum_middle_item_of_pages(invalid_pages_fixed.into_iter()))
 }
 
-pub fn task1() -> Result<i64, Box<dyn Error>> {
-    task1_run("data/day5_test.txt")
-}
-
-pub fn task2() -> Result<i64, Box<dyn Error>> {
-    task2_run("data/day5_test.txt")
+/// Human readable view of the ordering rules, for one page or a summary of all of them.
+pub fn describe_rules(path: &str, page: Option<i64>) -> Result<String, Box<dyn Error>> {
+    let lines = read_lines_from_file_v2(path);
+    let input_data = InputData::from_iter(lines)?;
+    let rules = &input_data.ordering_rules;
+
+    let Some(page) = page else {
+        let mut pages: Vec<_> = rules.keys().copied().collect();
+        pages.sort_unstable();
+        return Ok(format!(
+            "{} pages with rules, {} updates: {:?}",
+            pages.len(),
+            input_data.pages.len(),
+            pages
+        ));
+    };
+
+    let mut before = rules.get(&page).cloned().unwrap_or_default();
+    let mut after: Vec<_> = rules
+        .iter()
+        .filter(|(_, deps)| deps.contains(&page))
+        .map(|(value, _)| *value)
+        .collect();
+    before.sort_unstable();
+    after.sort_unstable();
+
+    Ok(format!(
+        "{} comes after {:?}\n{} comes before {:?}",
+        page, before, page, after
+    ))
 }
 
 
 
 #[cfg(test)]
 mod tests {
+    use crate::prop::{check, vec_of};
+
     use super::*;
 
 
@@ -264,6 +315,17 @@ const TEST_DATA: &'static [&'static str] = &[
         )
     }
 
+    #[test]
+    fn describe_rules_test() {
+        assert_eq!(
+            "29 comes after [47, 53, 61, 75, 97]\n29 comes before [13]",
+            describe_rules("data/day5_test.txt", Some(29)).unwrap()
+        );
+        assert!(describe_rules("data/day5_test.txt", None)
+            .unwrap()
+            .starts_with("6 pages with rules, 6 updates"));
+    }
+
     #[test]
     fn task1_test_data_test() {
         assert_eq!(143, task1_run("data/day5_test.txt").unwrap())
@@ -283,4 +345,32 @@ const TEST_DATA: &'static [&'static str] = &[
     fn task2_test() {
         assert_eq!(4260, task2_run("data/day5.txt").unwrap())
     }
+
+    #[test]
+    fn to_sorted_respects_rules_prop() {
+        // (page order the rules are derived from, update to sort)
+        check(
+            "day5 to_sorted respects every rule",
+            |rng| {
+                let mut order: Vec<i64> = (10..30).collect();
+                rng.shuffle(&mut order);
+                let update = vec_of(rng, 0..10, |rng| *rng.choose(&order));
+                (order, update)
+            },
+            |(order, update): &(Vec<i64>, Vec<i64>)| {
+                let mut ordering_rules = OrderingRules::new();
+                for (idx, value) in order.iter().enumerate() {
+                    ordering_rules.insert(*value, order[..idx].to_vec());
+                }
+
+                let sorted = PageToProduce {
+                    values: update.clone(),
+                }
+                .to_sorted(&ordering_rules);
+
+                let position = |value: &i64| order.iter().position(|o| o == value);
+                sorted.values.windows(2).all(|w| position(&w[0]) <= position(&w[1]))
+            },
+        );
+    }
 }
@@ -1,6 +1,18 @@
 use std::{collections::HashSet, error::Error, fmt, hash::Hash};
 
-use crate::file_utils::read_lines_from_file_v2;
+use crate::{
+    file_utils::read_lines_from_file_v2,
+    lint::{InputSpec, Layout},
+    progress::Progress,
+    trace,
+};
+
+pub const INPUT_SPEC: InputSpec = InputSpec {
+    layout: Layout::Grid,
+    allowed_chars: Some(".#^"),
+    exact_counts: &[('^', 1)],
+    min_real_bytes: 4096,
+};
 
 #[derive(Eq, Hash, PartialEq, Copy, Clone)]
 struct Coord {
@@ -156,7 +168,7 @@ fn lines_into_guard_and_map(
     Ok((Guard::new(guard_pos), a_map))
 }
 
-fn task1_run(path: &str) -> Result<i64, Box<dyn Error>> {
+pub fn task1_run(path: &str) -> Result<i64, Box<dyn Error>> {
     let lines = read_lines_from_file_v2(path);
     let (mut guard, map) = lines_into_guard_and_map(lines)?;
 
@@ -167,10 +179,11 @@ fn task1_run(path: &str) -> Result<i64, Box<dyn Error>> {
         guard.step(&map);
     }
 
+    trace::debug!("guard left the map at {}", guard.position);
     Ok(visited.len().try_into()?)
 }
 
-fn task2_run(path: &str) -> Result<i64, Box<dyn Error>> {
+pub fn task2_run(path: &str) -> Result<i64, Box<dyn Error>> {
     let lines = read_lines_from_file_v2(path);
     let (mut guard, mut map) = lines_into_guard_and_map(lines)?;
 
@@ -182,6 +195,8 @@ fn task2_run(path: &str) -> Result<i64, Box<dyn Error>> {
     }
 
     let mut cycles_count = 0;
+    trace::info!("checking {} candidate obstacles", visited.len());
+    let mut progress = Progress::new("day6 obstacles", visited.len());
 
     for coord_visited in visited {
         map.add_obstacle(coord_visited);
@@ -196,28 +211,51 @@ fn task2_run(path: &str) -> Result<i64, Box<dyn Error>> {
 
             if route_len > 2 * map.max_x * map.max_y {
                 // if route len is > 2XY then it must be a cycle
+                trace::trace!("obstacle at {} causes a cycle", coord_visited);
                 cycles_count += 1;
                 break;
             }
         }
 
         map.remove_obstacle(coord_visited);
+        progress.inc(1);
     }
 
+    trace::info!("{} candidate obstacles cause a cycle", cycles_count);
     Ok(cycles_count)
 }
 
-pub fn task1() -> Result<i64, Box<dyn Error>> {
-    task1_run("data/day6_test.txt")
-}
-
-pub fn task2() -> Result<i64, Box<dyn Error>> {
-    task2_run("data/day6.txt")
+/// Guard start, map bounds and obstacles sorted by row, as the solvers see them.
+pub fn describe_map(path: &str) -> Result<String, Box<dyn Error>> {
+    let lines = read_lines_from_file_v2(path);
+    let (guard, map) = lines_into_guard_and_map(lines)?;
+
+    let mut obstacles: Vec<_> = map.obstacles.iter().collect();
+    obstacles.sort_by_key(|o| (o.y, o.x));
+    let obstacles: Vec<_> = obstacles.iter().map(|o| o.to_string()).collect();
+
+    Ok(format!(
+        "guard at {}, bounds x 0..={} y 0..={}\n{} obstacles: {}",
+        guard.start_position,
+        map.max_x,
+        map.max_y,
+        obstacles.len(),
+        obstacles.join(" ")
+    ))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    #[test]
+    fn describe_map_test() {
+        let description = describe_map("data/day6_test.txt").unwrap();
+        assert!(description.starts_with(
+            "guard at (4, 6), bounds x 0..=9 y 0..=9\n8 obstacles: (4, 0) (9, 1)"
+        ));
+    }
+
     #[test]
     fn task1_test_data_test() {
         assert_eq!(41, task1_run("data/day6_test.txt").unwrap())
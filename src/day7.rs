@@ -1,6 +1,20 @@
 use std::{collections::HashSet, error::Error, fmt, hash::Hash};
 
-use crate::file_utils::read_lines_from_file_v2;
+use crate::{
+    file_utils::read_lines_from_file_v2,
+    lint::{InputSpec, Layout, LinePattern},
+    trace,
+};
+
+pub const INPUT_SPEC: InputSpec = InputSpec {
+    layout: Layout::Lines(LinePattern {
+        description: "a result, a colon and numbers",
+        regex: r"^\d+: \d+( \d+)*$",
+    }),
+    allowed_chars: None,
+    exact_counts: &[],
+    min_real_bytes: 4096,
+};
 
 fn is_eq_possible(result: &i64, ops: &[i64]) -> bool {
     if ops.len() == 1 {
@@ -17,7 +31,7 @@ fn is_eq_possible(result: &i64, ops: &[i64]) -> bool {
         let new_ops: Vec<i64> = std::iter::once(new_op)
             .chain(rest.iter().cloned())
             .collect();
-        is_eq_possible_v2(result, &new_ops)
+        is_eq_possible(result, &new_ops)
     })
 }
 
@@ -47,6 +61,39 @@ fn is_eq_possible_v2(result: &i64, ops: &[i64]) -> bool {
     })
 }
 
+/// Concatenates digits without going through a string, `None` if the result overflows.
+fn concat_i64_arithmetic(a: i64, b: i64) -> Option<i64> {
+    let mut shift: i64 = 10;
+    while shift <= b {
+        shift = shift.checked_mul(10)?;
+    }
+    a.checked_mul(shift)?.checked_add(b)
+}
+
+fn is_eq_possible_v3(result: &i64, ops: &[i64]) -> bool {
+    if ops.len() == 1 {
+        return *result == ops[0];
+    }
+
+    let op1 = ops[0];
+    let op2 = ops[1];
+    let rest = &ops[2..];
+
+    // An overflowing concatenation cannot be the result, like an unparseable one.
+    let new_ops_variants = [
+        Some(op1 + op2),
+        Some(op1 * op2),
+        concat_i64_arithmetic(op1, op2),
+    ];
+
+    new_ops_variants.iter().flatten().any(|&new_op| {
+        let new_ops: Vec<i64> = std::iter::once(new_op)
+            .chain(rest.iter().cloned())
+            .collect();
+        is_eq_possible_v3(result, &new_ops)
+    })
+}
+
 fn task_run(path: &str, checker: fn(&i64, &[i64]) -> bool) -> Result<i64, Box<dyn Error>> {
     let lines = read_lines_from_file_v2(path);
 
@@ -63,32 +110,69 @@ fn task_run(path: &str, checker: fn(&i64, &[i64]) -> bool) -> Result<i64, Box<dy
 
             Some((expected_result, ops))
         })
-        .filter(|(r, ops)| checker(r, ops))
+        .filter(|(r, ops)| {
+            let matched = checker(r, ops);
+            if matched {
+                trace::debug!("equation matched: {}: {:?}", r, ops);
+            }
+            matched
+        })
         .map(|(r, _)| r)
         .sum();
 
     Ok(sum)
 }
 
-fn task1_run(path: &str) -> Result<i64, Box<dyn Error>> {
+pub fn task1_run(path: &str) -> Result<i64, Box<dyn Error>> {
     task_run(path, is_eq_possible)
 }
 
-fn task2_run(path: &str) -> Result<i64, Box<dyn Error>> {
+pub fn task2_run(path: &str) -> Result<i64, Box<dyn Error>> {
     task_run(path, is_eq_possible_v2)
 }
 
-pub fn task1() -> Result<i64, Box<dyn Error>> {
-    task1_run("data/day7_test.txt")
-}
-
-pub fn task2() -> Result<i64, Box<dyn Error>> {
-    task2_run("data/day7_test.txt")
+pub fn task2_arithmetic_concat_run(path: &str) -> Result<i64, Box<dyn Error>> {
+    task_run(path, is_eq_possible_v3)
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::prop::{check, vec_of};
+
     use super::*;
+
+    /// Tries every operator combination left to right, `ops_count` being 2 or 3 (with concat).
+    fn is_eq_possible_exhaustive(result: i64, ops: &[i64], ops_count: u32) -> bool {
+        let combinations = ops_count.pow(ops.len() as u32 - 1);
+
+        (0..combinations).any(|mut combination| {
+            let value = ops[1..].iter().fold(ops[0], |acc, &op| {
+                let operator = combination % ops_count;
+                combination /= ops_count;
+                match operator {
+                    0 => acc + op,
+                    1 => acc * op,
+                    _ => concat_i64(acc, op),
+                }
+            });
+            value == result
+        })
+    }
+
+    fn gen_equation(rng: &mut crate::rng::Rng) -> (i64, Vec<i64>) {
+        let ops = vec_of(rng, 1..6, |rng| rng.range(1..20));
+        let result = if rng.chance(0.5) {
+            // Some random combination, so matches are common.
+            ops[1..].iter().fold(ops[0], |acc, &op| match rng.index(3) {
+                0 => acc + op,
+                1 => acc * op,
+                _ => concat_i64(acc, op),
+            })
+        } else {
+            rng.range(1..5000)
+        };
+        (result, ops)
+    }
     #[test]
     fn task1_test_data_test() {
         assert_eq!(3749, task1_run("data/day7_test.txt").unwrap())
@@ -96,7 +180,7 @@ mod tests {
 
     #[test]
     fn task1_test() {
-        assert_eq!(5067, task1_run("data/day7.txt").unwrap())
+        assert_eq!(2654749936343, task1_run("data/day7.txt").unwrap())
     }
 
     #[test]
@@ -108,4 +192,45 @@ mod tests {
     fn task2_test() {
         assert_eq!(124060392153684, task2_run("data/day7.txt").unwrap())
     }
+
+    #[test]
+    fn concat_i64_arithmetic_test() {
+        for (a, b) in [(1, 0), (12, 345), (15, 6), (7, 10), (99, 99)] {
+            assert_eq!(Some(concat_i64(a, b)), concat_i64_arithmetic(a, b));
+        }
+        assert_eq!(None, concat_i64_arithmetic(1, i64::MAX));
+        assert_eq!(None, concat_i64_arithmetic(i64::MAX / 10, 12));
+        assert_eq!(Some(i64::MAX), concat_i64_arithmetic(922337203685477580, 7));
+    }
+
+    #[test]
+    fn task2_arithmetic_concat_test_data_test() {
+        assert_eq!(11387, task2_arithmetic_concat_run("data/day7_test.txt").unwrap())
+    }
+
+    #[test]
+    fn is_eq_possible_matches_enumeration_prop() {
+        check(
+            "day7 is_eq_possible agrees with enumerating + and *",
+            gen_equation,
+            |(result, ops): &(i64, Vec<i64>)| {
+                ops.is_empty()
+                    || ops.iter().any(|&op| op < 1)
+                    || is_eq_possible(result, ops) == is_eq_possible_exhaustive(*result, ops, 2)
+            },
+        );
+    }
+
+    #[test]
+    fn is_eq_possible_v2_matches_enumeration_prop() {
+        check(
+            "day7 is_eq_possible_v2 agrees with enumerating +, * and ||",
+            gen_equation,
+            |(result, ops): &(i64, Vec<i64>)| {
+                ops.is_empty()
+                    || ops.iter().any(|&op| op < 1)
+                    || is_eq_possible_v2(result, ops) == is_eq_possible_exhaustive(*result, ops, 3)
+            },
+        );
+    }
 }
@@ -0,0 +1,252 @@
+use std::{
+    ffi::{c_char, CStr},
+    panic::{self, AssertUnwindSafe},
+    slice,
+    sync::OnceLock,
+};
+
+use crate::{file_utils::with_memory_input, task_registry::TaskRegistry, tasks_registry};
+
+pub const AOC_OK: i32 = 0;
+pub const AOC_ERR_NULL_POINTER: i32 = 1;
+pub const AOC_ERR_INVALID_UTF8: i32 = 2;
+pub const AOC_ERR_UNKNOWN_TASK: i32 = 3;
+pub const AOC_ERR_SOLVER_FAILED: i32 = 4;
+pub const AOC_ERR_SOLVER_PANICKED: i32 = 5;
+pub const AOC_ERR_BUFFER_TOO_SMALL: i32 = 6;
+
+/// Every error code with its C name and message. Codes are part of the ABI, never renumber.
+const ERROR_CODES: &[(i32, &str, &CStr)] = &[
+    (AOC_OK, "AOC_OK", c"ok"),
+    (
+        AOC_ERR_NULL_POINTER,
+        "AOC_ERR_NULL_POINTER",
+        c"null pointer argument",
+    ),
+    (
+        AOC_ERR_INVALID_UTF8,
+        "AOC_ERR_INVALID_UTF8",
+        c"input is not valid UTF-8",
+    ),
+    (
+        AOC_ERR_UNKNOWN_TASK,
+        "AOC_ERR_UNKNOWN_TASK",
+        c"no such day and part",
+    ),
+    (
+        AOC_ERR_SOLVER_FAILED,
+        "AOC_ERR_SOLVER_FAILED",
+        c"solver returned an error",
+    ),
+    (
+        AOC_ERR_SOLVER_PANICKED,
+        "AOC_ERR_SOLVER_PANICKED",
+        c"solver panicked, is the input valid?",
+    ),
+    (
+        AOC_ERR_BUFFER_TOO_SMALL,
+        "AOC_ERR_BUFFER_TOO_SMALL",
+        c"output buffer is too small",
+    ),
+];
+
+fn registry() -> &'static TaskRegistry {
+    static REGISTRY: OnceLock<TaskRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(tasks_registry)
+}
+
+fn solve(day: u32, part: u32, input: &[u8]) -> Result<i64, i32> {
+    let task = registry()
+        .get(&format!("day{}_task{}", day, part))
+        .ok_or(AOC_ERR_UNKNOWN_TASK)?;
+    let input = std::str::from_utf8(input).map_err(|_| AOC_ERR_INVALID_UTF8)?;
+
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        with_memory_input(input, |path| (task.default_implementation().func)(path))
+    }))
+    .map_err(|_| AOC_ERR_SOLVER_PANICKED)?
+    .map_err(|_| AOC_ERR_SOLVER_FAILED)
+}
+
+/// Solves `day` part `part` on `input_len` bytes of UTF-8 input and stores the answer in
+/// `*out_buf`. Returns `AOC_OK` or one of the `AOC_ERR_*` codes, `*out_buf` is only written
+/// on success.
+///
+/// # Safety
+///
+/// `input_ptr` must point to `input_len` readable bytes and `out_buf` to a writable `i64`.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u32,
+    part: u32,
+    input_ptr: *const u8,
+    input_len: usize,
+    out_buf: *mut i64,
+) -> i32 {
+    if input_ptr.is_null() || out_buf.is_null() {
+        return AOC_ERR_NULL_POINTER;
+    }
+    let input = slice::from_raw_parts(input_ptr, input_len);
+
+    match solve(day, part, input) {
+        Ok(answer) => {
+            *out_buf = answer;
+            AOC_OK
+        }
+        Err(code) => code,
+    }
+}
+
+/// Writes the task ids, one per line, as a NUL-terminated string into `out_buf`. The size
+/// needed including the NUL always goes to `*out_len`, so callers can retry with a larger
+/// buffer after `AOC_ERR_BUFFER_TOO_SMALL`.
+///
+/// # Safety
+///
+/// `out_buf` must point to `buf_len` writable bytes, or be null when `buf_len` is 0.
+/// `out_len` must point to a writable `size_t`.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_list_tasks(
+    out_buf: *mut c_char,
+    buf_len: usize,
+    out_len: *mut usize,
+) -> i32 {
+    if out_len.is_null() || (out_buf.is_null() && buf_len > 0) {
+        return AOC_ERR_NULL_POINTER;
+    }
+
+    let ids: Vec<_> = registry().tasks().iter().map(|t| t.id.as_str()).collect();
+    let listing = ids.join("\n");
+    *out_len = listing.len() + 1;
+
+    if buf_len < listing.len() + 1 {
+        return AOC_ERR_BUFFER_TOO_SMALL;
+    }
+    let out = slice::from_raw_parts_mut(out_buf.cast::<u8>(), buf_len);
+    out[..listing.len()].copy_from_slice(listing.as_bytes());
+    out[listing.len()] = 0;
+    AOC_OK
+}
+
+/// Static message for an error code, never null and never to be freed.
+#[no_mangle]
+pub extern "C" fn aoc_error_message(code: i32) -> *const c_char {
+    ERROR_CODES
+        .iter()
+        .find(|(value, _, _)| *value == code)
+        .map_or(c"unknown error code", |(_, _, message)| message)
+        .as_ptr()
+}
+
+/// C declarations of everything above, checked against `include/aoc2024.h` by the tests.
+pub fn c_header() -> String {
+    let mut header = String::from(
+        "/* Generated from src/ffi.rs, do not edit. */\n\
+         #ifndef AOC2024_H\n\
+         #define AOC2024_H\n\
+         \n\
+         #include <stddef.h>\n\
+         #include <stdint.h>\n\
+         \n",
+    );
+
+    for (value, name, message) in ERROR_CODES {
+        header.push_str(&format!(
+            "#define {} {} /* {} */\n",
+            name,
+            value,
+            message.to_str().unwrap()
+        ));
+    }
+
+    header.push_str(
+        "\n\
+         #ifdef __cplusplus\n\
+         extern \"C\" {\n\
+         #endif\n\
+         \n\
+         int32_t aoc_solve(uint32_t day, uint32_t part, const uint8_t *input_ptr,\n\
+         \x20                 size_t input_len, int64_t *out_buf);\n\
+         \n\
+         int32_t aoc_list_tasks(char *out_buf, size_t buf_len, size_t *out_len);\n\
+         \n\
+         const char *aoc_error_message(int32_t code);\n\
+         \n\
+         #ifdef __cplusplus\n\
+         }\n\
+         #endif\n\
+         \n\
+         #endif /* AOC2024_H */\n",
+    );
+    header
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, ptr};
+
+    use super::*;
+
+    const HEADER_PATH: &str = "include/aoc2024.h";
+
+    #[test]
+    fn aoc_solve_test() {
+        let input = fs::read("data/day1_test.txt").unwrap();
+        let mut answer = 0;
+
+        let code = unsafe { aoc_solve(1, 1, input.as_ptr(), input.len(), &mut answer) };
+        assert_eq!((AOC_OK, 11), (code, answer));
+
+        let code = unsafe { aoc_solve(1, 2, input.as_ptr(), input.len(), &mut answer) };
+        assert_eq!((AOC_OK, 31), (code, answer));
+
+        let code = unsafe { aoc_solve(99, 1, input.as_ptr(), input.len(), &mut answer) };
+        assert_eq!(AOC_ERR_UNKNOWN_TASK, code);
+
+        let invalid = [0xff, 0xfe];
+        let code = unsafe { aoc_solve(1, 1, invalid.as_ptr(), invalid.len(), &mut answer) };
+        assert_eq!(AOC_ERR_INVALID_UTF8, code);
+
+        let code = unsafe { aoc_solve(1, 1, ptr::null(), 0, &mut answer) };
+        assert_eq!(AOC_ERR_NULL_POINTER, code);
+        assert_eq!(31, answer);
+    }
+
+    #[test]
+    fn aoc_list_tasks_test() {
+        let mut len = 0;
+        let code = unsafe { aoc_list_tasks(ptr::null_mut(), 0, &mut len) };
+        assert_eq!(AOC_ERR_BUFFER_TOO_SMALL, code);
+
+        let mut buf = vec![0 as c_char; len];
+        let code = unsafe { aoc_list_tasks(buf.as_mut_ptr(), buf.len(), &mut len) };
+        assert_eq!(AOC_OK, code);
+
+        let listing = unsafe { CStr::from_ptr(buf.as_ptr()) }.to_str().unwrap();
+        assert!(listing.starts_with("day1_task1\nday1_task2\n"));
+        assert_eq!(registry().tasks().len(), listing.lines().count());
+    }
+
+    #[test]
+    fn aoc_error_message_test() {
+        let message = unsafe { CStr::from_ptr(aoc_error_message(AOC_ERR_UNKNOWN_TASK)) };
+        assert_eq!(c"no such day and part", message);
+
+        let message = unsafe { CStr::from_ptr(aoc_error_message(-1)) };
+        assert_eq!(c"unknown error code", message);
+    }
+
+    /// Set `AOC_UPDATE_HEADER=1` to regenerate the header after changing the API.
+    #[test]
+    fn c_header_is_up_to_date_test() {
+        if env::var_os("AOC_UPDATE_HEADER").is_some() {
+            fs::write(HEADER_PATH, c_header()).unwrap();
+        }
+        assert_eq!(
+            c_header(),
+            fs::read_to_string(HEADER_PATH).unwrap(),
+            "{} is stale, rerun with AOC_UPDATE_HEADER=1",
+            HEADER_PATH
+        );
+    }
+}
@@ -1,17 +1,140 @@
 use std::{
-    fs::File,
-    io::{self, BufRead, BufReader},
+    collections::HashMap,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Cursor},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, OnceLock,
+    },
 };
 
+const MEMORY_PREFIX: &str = "memory:";
+
+static NEXT_MEMORY_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Inputs that never touched the disk, keyed by their `memory:<id>` path.
+fn memory_inputs() -> &'static Mutex<HashMap<String, Arc<str>>> {
+    static INPUTS: OnceLock<Mutex<HashMap<String, Arc<str>>>> = OnceLock::new();
+    INPUTS.get_or_init(Default::default)
+}
+
+fn memory_input(path: &str) -> Option<Arc<str>> {
+    if !path.starts_with(MEMORY_PREFIX) {
+        return None;
+    }
+    memory_inputs().lock().unwrap().get(path).cloned()
+}
+
+/// Makes `content` readable through a synthetic path for the duration of `f`, so solvers
+/// taking an input path can run on input held in memory.
+pub fn with_memory_input<T>(content: &str, f: impl FnOnce(&str) -> T) -> T {
+    let path = format!(
+        "{}{}",
+        MEMORY_PREFIX,
+        NEXT_MEMORY_ID.fetch_add(1, Ordering::Relaxed)
+    );
+    memory_inputs()
+        .lock()
+        .unwrap()
+        .insert(path.clone(), Arc::from(content));
+
+    // Removes the input even if `f` panics.
+    struct Cleanup<'a>(&'a str);
+    impl Drop for Cleanup<'_> {
+        fn drop(&mut self) {
+            if let Ok(mut inputs) = memory_inputs().lock() {
+                inputs.remove(self.0);
+            }
+        }
+    }
+    let _cleanup = Cleanup(&path);
+
+    f(&path)
+}
+
+#[cfg(feature = "embed-inputs")]
+mod embedded {
+    include!(concat!(env!("OUT_DIR"), "/embedded_inputs.rs"));
+}
+
+/// `data/*.txt` as compiled into the binary, used when the file is not on disk so a release
+/// build runs from any directory.
+#[cfg(feature = "embed-inputs")]
+fn embedded_input(path: &str) -> Option<&'static str> {
+    if std::path::Path::new(path).exists() {
+        return None;
+    }
+    let path = path.trim_start_matches("./");
+    embedded::EMBEDDED_INPUTS
+        .iter()
+        .find(|(name, _)| *name == path)
+        .map(|(_, content)| *content)
+}
+
+#[cfg(not(feature = "embed-inputs"))]
+fn embedded_input(_path: &str) -> Option<&'static str> {
+    None
+}
+
+/// Buffered reader over an input, for solvers streaming it rather than reading it whole.
+pub fn buf_reader(path: &str) -> io::Result<Box<dyn BufRead>> {
+    if let Some(content) = memory_input(path) {
+        return Ok(Box::new(Cursor::new(content.as_bytes().to_vec())));
+    }
+    match embedded_input(path) {
+        Some(content) => Ok(Box::new(Cursor::new(content.as_bytes()))),
+        None => Ok(Box::new(BufReader::new(File::open(path)?))),
+    }
+}
+
+fn open(path: &str) -> Box<dyn BufRead> {
+    buf_reader(path).unwrap()
+}
+
+pub fn read_to_string(path: &str) -> io::Result<String> {
+    if let Some(content) = memory_input(path) {
+        return Ok(content.to_string());
+    }
+    match embedded_input(path) {
+        Some(content) => Ok(content.to_string()),
+        None => fs::read_to_string(path),
+    }
+}
+
 pub fn read_lines_from_file(path: &str) -> impl Iterator<Item = io::Result<String>> {
-    let file = File::open(path).unwrap();
-    let reader = BufReader::new(file);
+    let reader = open(path);
     reader.lines()
 }
 
-
 pub fn read_lines_from_file_v2(path: &str) -> impl Iterator<Item = String> {
-    let file = File::open(path).unwrap();
-    let reader = BufReader::new(file);
+    let reader = open(path);
     reader.lines().map(|l| l.unwrap())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with_memory_input_test() {
+        let path = with_memory_input("1 2\n3 4\n", |path| {
+            let lines: Vec<_> = read_lines_from_file_v2(path).collect();
+            assert_eq!(vec!["1 2", "3 4"], lines);
+            assert_eq!("1 2\n3 4\n", read_to_string(path).unwrap());
+            path.to_string()
+        });
+
+        assert!(memory_input(&path).is_none());
+        assert!(read_to_string(&path).is_err());
+    }
+
+    #[cfg(feature = "embed-inputs")]
+    #[test]
+    fn embedded_input_test() {
+        let on_disk = fs::read_to_string("data/day5_test.txt").unwrap();
+
+        assert_eq!(None, embedded_input("data/day5_test.txt"));
+        assert!(embedded::EMBEDDED_INPUTS.contains(&("data/day5_test.txt", on_disk.as_str())));
+        assert_eq!(None, embedded_input("data/missing.txt"));
+    }
+}
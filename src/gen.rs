@@ -0,0 +1,381 @@
+use std::{
+    error::Error,
+    fmt::Write as _,
+    fs,
+    io::{self, Write},
+};
+
+use crate::{args::Args, rng::Rng};
+
+const DEFAULT_SEED: u64 = 2024;
+
+/// Two columns of five digit location IDs, with repeats so similarity is non-trivial.
+fn gen_day1(rng: &mut Rng, size: usize) -> String {
+    let left: Vec<i64> = (0..size).map(|_| rng.range(10000..100000)).collect();
+    let mut out = String::new();
+
+    for &l in &left {
+        let r = if rng.chance(0.3) {
+            *rng.choose(&left)
+        } else {
+            rng.range(10000..100000)
+        };
+        writeln!(out, "{}   {}", l, r).unwrap();
+    }
+    out
+}
+
+/// Reports that are safe, safe after removing one level, or random.
+fn gen_day2(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+
+    for _ in 0..size {
+        let len = rng.range(5..9) as usize;
+        let direction = if rng.chance(0.5) { 1 } else { -1 };
+        // Descending walks start high enough to stay non-negative through the perturbation.
+        let mut level = if direction < 0 {
+            rng.range(30..90)
+        } else {
+            rng.range(10..90)
+        };
+        let mut levels = Vec::with_capacity(len);
+
+        for _ in 0..len {
+            levels.push(level);
+            level += direction * rng.range(1..4);
+        }
+
+        match rng.index(3) {
+            0 => (),
+            1 => {
+                let idx = rng.index(len);
+                levels[idx] += rng.range(-5..6);
+            }
+            _ => levels.iter_mut().for_each(|l| *l = rng.range(1..100)),
+        }
+
+        let line: Vec<_> = levels.iter().map(i64::to_string).collect();
+        writeln!(out, "{}", line.join(" ")).unwrap();
+    }
+    out
+}
+
+/// `size` instructions buried in noise and near-miss decoys, split over a few lines.
+fn gen_day3(rng: &mut Rng, size: usize) -> String {
+    const NOISE: &[u8] = b"!@#$%^&*()[]{}<>,;:' +-?/~xmulwhydon'twhat";
+    const DECOYS: &[&str] = &[
+        "mul(4*",
+        "mul(6,9!",
+        "?(12,34)",
+        "mul ( 2 , 4 )",
+        "mul[3,7]",
+        "do_not_",
+        "mul(1234,5)",
+        "don't",
+        "do(",
+    ];
+
+    let mut out = String::new();
+    for idx in 0..size {
+        for _ in 0..rng.range(0..8) {
+            out.push(*rng.choose(NOISE) as char);
+        }
+
+        match rng.index(10) {
+            0 => out.push_str("do()"),
+            1 => out.push_str("don't()"),
+            2 | 3 => out.push_str(rng.choose::<&str>(DECOYS)),
+            _ => write!(out, "mul({},{})", rng.range(0..1000), rng.range(0..1000)).unwrap(),
+        }
+
+        if idx % 500 == 499 {
+            out.push('\n');
+        }
+    }
+    if !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+/// Square letter grid of `size` x `size` made of XMAS letters.
+fn gen_day4(rng: &mut Rng, size: usize) -> String {
+    const LETTERS: &[u8] = b"XMAS";
+
+    let mut out = String::with_capacity(size * (size + 1));
+    for _ in 0..size {
+        for _ in 0..size {
+            out.push(*rng.choose(LETTERS) as char);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Complete ordering rules over a hidden page order, followed by `size` updates.
+fn gen_day5(rng: &mut Rng, size: usize) -> String {
+    let mut pages: Vec<i64> = (10..100).collect();
+    rng.shuffle(&mut pages);
+    pages.truncate(49);
+
+    let mut out = String::new();
+    let mut rules = Vec::new();
+    for (i, before) in pages.iter().enumerate() {
+        for after in &pages[i + 1..] {
+            rules.push((*before, *after));
+        }
+    }
+    rng.shuffle(&mut rules);
+    for (before, after) in rules {
+        writeln!(out, "{}|{}", before, after).unwrap();
+    }
+    out.push('\n');
+
+    for _ in 0..size {
+        let len = 2 * rng.range(2..12) as usize + 1;
+        let mut positions: Vec<usize> = (0..pages.len()).collect();
+        rng.shuffle(&mut positions);
+        positions.truncate(len);
+
+        if rng.chance(0.5) {
+            positions.sort_unstable();
+        }
+
+        let update: Vec<_> = positions.iter().map(|&p| pages[p].to_string()).collect();
+        writeln!(out, "{}", update.join(",")).unwrap();
+    }
+    out
+}
+
+/// Whether a guard starting at `start` (row, column) facing up walks off `grid`. Every
+/// position and direction is visited at most once before the guard repeats itself.
+fn guard_leaves(grid: &[Vec<char>], start: (usize, usize)) -> bool {
+    const MOVES: [(i64, i64); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+    let size = grid.len() as i64;
+    let (mut row, mut col) = (start.0 as i64, start.1 as i64);
+    let mut direction = 0;
+
+    for _ in 0..4 * grid.len() * grid.len() {
+        let (next_row, next_col) = (row + MOVES[direction].0, col + MOVES[direction].1);
+        if !(0..size).contains(&next_row) || !(0..size).contains(&next_col) {
+            return true;
+        }
+        if grid[next_row as usize][next_col as usize] == '#' {
+            direction = (direction + 1) % 4;
+        } else {
+            (row, col) = (next_row, next_col);
+        }
+    }
+    false
+}
+
+/// Square `size` x `size` lab map with scattered obstacles and the guard facing up, redrawn
+/// until the guard walks off the map.
+fn gen_day6(rng: &mut Rng, size: usize) -> String {
+    const ATTEMPTS: usize = 20;
+    let size = size.max(2);
+
+    let grid = (0..)
+        .map(|attempt| {
+            let mut grid = vec![vec!['.'; size]; size];
+            for row in grid.iter_mut() {
+                for cell in row.iter_mut() {
+                    if rng.chance(0.03) {
+                        *cell = '#';
+                    }
+                }
+            }
+
+            // Map bounds come from obstacles, so pin the far corner.
+            grid[size - 1][size - 1] = '#';
+            let guard = (rng.index(size - 1), rng.index(size - 1));
+            grid[guard.0][guard.1] = '^';
+
+            // Should the draws keep trapping the guard, clear its way straight up.
+            if attempt + 1 == ATTEMPTS {
+                for row in grid.iter_mut().take(guard.0) {
+                    row[guard.1] = '.';
+                }
+            }
+            (grid, guard)
+        })
+        .find(|(grid, guard)| guard_leaves(grid, *guard))
+        .map(|(grid, _)| grid)
+        .unwrap();
+
+    let mut out = String::with_capacity(size * (size + 1));
+    for row in grid {
+        out.extend(row);
+        out.push('\n');
+    }
+    out
+}
+
+/// Calibration equations built from random operators, with some left unsolvable.
+fn gen_day7(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+
+    for _ in 0..size {
+        let operands: Vec<i64> = (0..rng.range(2..9)).map(|_| rng.range(1..100)).collect();
+
+        let mut result = operands[0];
+        for &operand in &operands[1..] {
+            result = match rng.index(3) {
+                0 => result + operand,
+                1 => result * operand,
+                _ => format!("{}{}", result, operand).parse().unwrap(),
+            };
+        }
+
+        if rng.chance(0.3) {
+            result += rng.range(1..10);
+        }
+
+        let operands: Vec<_> = operands.iter().map(i64::to_string).collect();
+        writeln!(out, "{}: {}", result, operands.join(" ")).unwrap();
+    }
+    out
+}
+
+/// Generates a valid input for the day, deterministic for a given seed.
+pub fn generate(day: u32, size: usize, seed: u64) -> Result<String, Box<dyn Error>> {
+    let mut rng = Rng::new(seed);
+    let generator = match day {
+        1 => gen_day1,
+        2 => gen_day2,
+        3 => gen_day3,
+        4 => gen_day4,
+        5 => gen_day5,
+        6 => gen_day6,
+        7 => gen_day7,
+        _ => return Err(format!("no input generator for day {}", day).into()),
+    };
+    Ok(generator(&mut rng, size))
+}
+
+/// `gen <day> --size N [--seed S] [--out PATH]` - writes a synthetic input.
+pub fn run(args: &Args) -> Result<(), Box<dyn Error>> {
+    let day: u32 = args
+        .positional()
+        .first()
+        .ok_or("usage: gen <day> --size N [--seed S] [--out PATH]")?
+        .parse()?;
+    let size: usize = args.option("size").ok_or("--size is required")?.parse()?;
+    let seed = args.option_or("seed", DEFAULT_SEED)?;
+
+    let input = generate(day, size, seed)?;
+    match args.option("out") {
+        Some(path) => fs::write(path, input)?,
+        None => io::stdout().lock().write_all(input.as_bytes())?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, path::PathBuf, sync::mpsc, thread, time::Duration};
+
+    use crate::{day1, day2, day3, day4, day5, day6, day7, lint, task_registry::TaskFn};
+
+    use super::*;
+
+    fn write_generated(day: u32, size: usize) -> PathBuf {
+        let path =
+            env::temp_dir().join(format!("aoc2024_gen_day{}_{}.txt", day, std::process::id()));
+        fs::write(&path, generate(day, size, 42).unwrap()).unwrap();
+        path
+    }
+
+    #[test]
+    fn generate_is_deterministic_test() {
+        for day in 1..=7 {
+            assert_eq!(generate(day, 20, 1).unwrap(), generate(day, 20, 1).unwrap());
+            assert_ne!(generate(day, 20, 1).unwrap(), generate(day, 20, 2).unwrap());
+        }
+        assert!(generate(8, 20, 1).is_err());
+    }
+
+    #[test]
+    fn generated_inputs_are_solvable_test() {
+        let tasks: [(u32, TaskFn); 14] = [
+            (1, day1::task1_run),
+            (1, day1::task2_run),
+            (2, day2::task1_run),
+            (2, day2::task2_run),
+            (3, day3::task1_run),
+            (3, day3::task2_run),
+            (4, day4::task1_run),
+            (4, day4::task2_run),
+            (5, day5::task1_run),
+            (5, day5::task2_run),
+            (6, day6::task1_run),
+            (6, day6::task2_run),
+            (7, day7::task1_run),
+            (7, day7::task2_run),
+        ];
+
+        for (day, task) in tasks {
+            let path = write_generated(day, 30);
+            assert!(task(path.to_str().unwrap()).is_ok(), "day{} failed", day);
+        }
+    }
+
+    #[test]
+    fn generated_inputs_pass_lint_test() {
+        for day in 1..=7 {
+            for seed in 1..=5 {
+                let input = generate(day, 500, seed).unwrap();
+                let warnings = lint::lint(lint::spec(day).unwrap(), "generated.txt", &input);
+                assert!(
+                    warnings.is_empty(),
+                    "day{} seed {}: {:?}",
+                    day,
+                    seed,
+                    warnings
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn generated_day6_guard_leaves_test() {
+        for seed in 1..=40 {
+            let input = generate(6, 300, seed).unwrap();
+            let grid: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
+            let start = grid
+                .iter()
+                .enumerate()
+                .find_map(|(row, cells)| Some((row, cells.iter().position(|c| *c == '^')?)))
+                .unwrap();
+            assert!(guard_leaves(&grid, start), "seed {}", seed);
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for seed in 1..=10 {
+                let path = env::temp_dir().join(format!(
+                    "aoc2024_gen_day6_{}_{}.txt",
+                    seed,
+                    std::process::id()
+                ));
+                fs::write(&path, generate(6, 300, seed).unwrap()).unwrap();
+                let answer = day6::task1_run(path.to_str().unwrap());
+                fs::remove_file(&path).unwrap();
+                sender.send((seed, answer.is_ok())).unwrap();
+            }
+        });
+        for _ in 1..=10 {
+            let (seed, ok) = receiver
+                .recv_timeout(Duration::from_secs(10))
+                .expect("day6 task1 did not finish on a generated map");
+            assert!(ok, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn gen_day5_rules_are_complete_test() {
+        let input = generate(5, 10, 3).unwrap();
+        let rules = input.lines().take_while(|l| !l.is_empty()).count();
+        assert_eq!(49 * 48 / 2, rules);
+    }
+}
@@ -0,0 +1,78 @@
+use task_registry::TaskRegistry;
+
+mod day1;
+mod day2;
+mod day3;
+mod day4;
+mod day5;
+mod day6;
+mod day7;
+
+mod alloc_stats;
+mod answers;
+pub mod args;
+pub mod batch;
+pub mod crosscheck;
+pub mod ffi;
+mod file_utils;
+pub mod gen;
+mod lint;
+#[cfg(test)]
+mod prop;
+pub mod progress;
+pub mod repl;
+mod report;
+mod rng;
+pub mod runner;
+pub mod serve;
+pub mod submit;
+pub mod task_registry;
+pub mod trace;
+pub mod watch;
+
+pub use day1::metrics;
+pub use day3::vm;
+
+/// Every task with the input it runs on by default.
+pub fn tasks_registry() -> TaskRegistry {
+    TaskRegistry::new(&[
+        ("day1_task1", day1::task1_run, "data/day1.txt"),
+        ("day1_task2", day1::task2_run, "data/day1.txt"),
+        ("day2_task1", day2::task1_run, "data/day2.txt"),
+        ("day2_task2", day2::task2_run, "data/day2_test.txt"),
+        ("day3_task1", day3::task1_run, "data/day3_test.txt"),
+        ("day3_task2", day3::task2_run, "data/day3_test.txt"),
+        ("day4_task1", day4::task1_run, "data/day4_test.txt"),
+        ("day4_task2", day4::task2_run, "data/day4_test.txt"),
+        ("day5_task1", day5::task1_run, "data/day5_test.txt"),
+        ("day5_task2", day5::task2_run, "data/day5_test.txt"),
+        ("day6_task1", day6::task1_run, "data/day6_test.txt"),
+        ("day6_task2", day6::task2_run, "data/day6.txt"),
+        ("day7_task1", day7::task1_run, "data/day7_test.txt"),
+        ("day7_task2", day7::task2_run, "data/day7_test.txt"),
+    ])
+    .with_implementations(&[
+        ("day1_task1", "external", day1::task1_external_run),
+        ("day1_task2", "external", day1::task2_external_run),
+        (
+            "day1_task2",
+            "counting_array",
+            day1::task2_counting_array_run,
+        ),
+        ("day1_task2", "sorted_merge", day1::task2_sorted_merge_run),
+        ("day1_task2", "hash_map", day1::task2_hash_map_run),
+        ("day2_task2", "naive", day2::task2_naive_run),
+        ("day2_task2", "no_alloc", day2::task2_no_alloc_run),
+        ("day3_task1", "ops", day3::task1_ops_run),
+        ("day3_task1", "scanner", day3::task1_scanner_run),
+        ("day3_task1", "wide", day3::task1_wide_run),
+        ("day3_task2", "ops", day3::task2_ops_run),
+        ("day3_task2", "scanner", day3::task2_scanner_run),
+        ("day3_task2", "wide", day3::task2_wide_run),
+        (
+            "day7_task2",
+            "arithmetic_concat",
+            day7::task2_arithmetic_concat_run,
+        ),
+    ])
+}
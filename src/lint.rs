@@ -0,0 +1,385 @@
+use std::{collections::HashMap, fmt};
+
+use regex::Regex;
+
+use crate::{
+    day1, day2, day3, day4, day5, day6, day7,
+    file_utils::read_to_string,
+    task_registry::{day_of, Task, TaskRegistry},
+};
+
+/// A regex every line of a kind must match, with a description for the warnings.
+pub struct LinePattern {
+    pub description: &'static str,
+    pub regex: &'static str,
+}
+
+pub enum Layout {
+    /// Every line matches the pattern.
+    Lines(LinePattern),
+    /// Blocks separated by a single blank line, each with its own line pattern.
+    Sections(&'static [LinePattern]),
+    /// All lines have the same width.
+    Grid,
+    /// No line structure.
+    Text,
+}
+
+/// What a day expects of its puzzle input.
+pub struct InputSpec {
+    pub layout: Layout,
+    /// Characters allowed besides newlines, `None` allows any printable ASCII.
+    pub allowed_chars: Option<&'static str>,
+    /// Characters that must appear exactly this many times.
+    pub exact_counts: &'static [(char, usize)],
+    /// Real inputs are at least this big, anything smaller is probably the sample.
+    pub min_real_bytes: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Warning {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl Warning {
+    fn new(message: impl Into<String>) -> Self {
+        Warning {
+            line: None,
+            message: message.into(),
+        }
+    }
+
+    fn at(line: usize, message: impl Into<String>) -> Self {
+        Warning {
+            line: Some(line),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+pub fn spec(day: u32) -> Option<&'static InputSpec> {
+    match day {
+        1 => Some(&day1::INPUT_SPEC),
+        2 => Some(&day2::INPUT_SPEC),
+        3 => Some(&day3::INPUT_SPEC),
+        4 => Some(&day4::INPUT_SPEC),
+        5 => Some(&day5::INPUT_SPEC),
+        6 => Some(&day6::INPUT_SPEC),
+        7 => Some(&day7::INPUT_SPEC),
+        _ => None,
+    }
+}
+
+/// Warns about every line of `lines` not matching `pattern`, `(number, line)` pairs.
+fn check_lines(lines: &[(usize, &str)], pattern: &LinePattern, truncated: bool) -> Vec<Warning> {
+    let regex = Regex::new(pattern.regex).unwrap();
+    let bad: Vec<_> = lines.iter().filter(|(_, l)| !regex.is_match(l)).collect();
+
+    match bad.as_slice() {
+        [] => Vec::new(),
+        [(number, _)] if truncated && Some(*number) == lines.last().map(|(n, _)| *n) => {
+            vec![Warning::at(
+                *number,
+                "last line is incomplete, the download may be truncated",
+            )]
+        }
+        [(number, line), rest @ ..] => vec![Warning::at(
+            *number,
+            format!(
+                "expected {}, found {:?}{}",
+                pattern.description,
+                line,
+                match rest.len() {
+                    0 => String::new(),
+                    n => format!(" ({} more lines like this)", n),
+                }
+            ),
+        )],
+    }
+}
+
+fn check_layout(layout: &Layout, content: &str, truncated: bool) -> Vec<Warning> {
+    let lines: Vec<(usize, &str)> = content
+        .lines()
+        .enumerate()
+        .map(|(i, l)| (i + 1, l))
+        .collect();
+
+    match layout {
+        Layout::Lines(pattern) => check_lines(&lines, pattern, truncated),
+        Layout::Sections(patterns) => {
+            let sections: Vec<_> = lines.split(|(_, l)| l.is_empty()).collect();
+            if sections.len() != patterns.len() {
+                return vec![Warning::new(format!(
+                    "expected {} sections separated by a blank line, found {}",
+                    patterns.len(),
+                    sections.len()
+                ))];
+            }
+            sections
+                .iter()
+                .zip(patterns.iter())
+                .flat_map(|(section, pattern)| check_lines(section, pattern, truncated))
+                .collect()
+        }
+        Layout::Grid => {
+            let Some((_, first)) = lines.first() else {
+                return Vec::new();
+            };
+            let width = first.chars().count();
+            lines
+                .iter()
+                .find(|(_, l)| l.chars().count() != width)
+                .map(|(number, l)| {
+                    let message = if truncated && *number == lines.len() {
+                        "last row is shorter, the download may be truncated".to_string()
+                    } else {
+                        format!("row is {} wide, expected {}", l.chars().count(), width)
+                    };
+                    Warning::at(*number, message)
+                })
+                .into_iter()
+                .collect()
+        }
+        Layout::Text => Vec::new(),
+    }
+}
+
+fn check_chars(spec: &InputSpec, content: &str) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+
+    for (number, line) in content.lines().enumerate() {
+        let unexpected = line.chars().find(|&c| match spec.allowed_chars {
+            Some(allowed) => !allowed.contains(c),
+            None => !(c.is_ascii_graphic() || c == ' '),
+        });
+        if let Some(c) = unexpected {
+            warnings.push(Warning::at(
+                number + 1,
+                format!("unexpected character {:?}", c),
+            ));
+            break;
+        }
+    }
+
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for c in content.chars() {
+        *counts.entry(c).or_default() += 1;
+    }
+    for (c, expected) in spec.exact_counts {
+        let found = counts.get(c).copied().unwrap_or(0);
+        if found != *expected {
+            warnings.push(Warning::new(format!(
+                "expected {} {:?}, found {}",
+                expected, c, found
+            )));
+        }
+    }
+    warnings
+}
+
+/// Whether `content` is too small to be a real puzzle input.
+pub fn looks_like_sample(spec: &InputSpec, content: &str) -> bool {
+    content.len() < spec.min_real_bytes
+}
+
+/// Checks raw input against the day's spec, `path` is only used to recognise sample files.
+pub fn lint(spec: &InputSpec, path: &str, content: &str) -> Vec<Warning> {
+    if content.trim().is_empty() {
+        return vec![Warning::new("input is empty")];
+    }
+
+    let mut warnings = Vec::new();
+    if content.contains('\r') {
+        warnings.push(Warning::new(
+            "CRLF line endings, convert the file with `dos2unix` or `sed -i 's/\\r$//'`",
+        ));
+    }
+    let content = content.replace("\r\n", "\n");
+
+    let truncated = !content.ends_with('\n');
+    if truncated {
+        warnings.push(Warning::new(
+            "no newline at the end, the download may be truncated",
+        ));
+    }
+    if content.ends_with("\n\n") {
+        let last_line = content.trim_end().lines().count();
+        warnings.push(Warning::at(
+            last_line + 1,
+            "trailing blank line, some solvers read it as an empty record",
+        ));
+    }
+
+    let is_sample_file = path.contains("_test");
+    if !is_sample_file && looks_like_sample(spec, &content) {
+        warnings.push(Warning::new(format!(
+            "only {} bytes, this looks like the sample input rather than the real one",
+            content.len()
+        )));
+    }
+
+    warnings.extend(check_layout(
+        &spec.layout,
+        content.trim_end_matches('\n'),
+        truncated,
+    ));
+    warnings.extend(check_chars(spec, &content));
+    warnings
+}
+
+/// Warns when a task defaults to a sample file while another part of the same day defaults
+/// to real data, which usually means the registry entry was never switched over.
+fn sample_input_warning(registry: &TaskRegistry, task: &Task) -> Option<Warning> {
+    if !task.input_path.contains("_test") {
+        return None;
+    }
+    let day = day_of(&task.id).ok()?;
+    let sibling = registry.tasks().iter().find(|t| {
+        t.id != task.id && day_of(&t.id).ok() == Some(day) && !t.input_path.contains("_test")
+    })?;
+
+    Some(Warning::new(format!(
+        "{} is registered with sample input {} while {} uses {}",
+        task.id, task.input_path, sibling.id, sibling.input_path
+    )))
+}
+
+/// Lints the input a task is about to run on and prints warnings to stderr.
+pub fn warn_about_input(registry: &TaskRegistry, task: &Task, input_path: &str) {
+    let Some(spec) = day_of(&task.id).ok().and_then(spec) else {
+        return;
+    };
+    // Unreadable inputs are reported by the solver itself.
+    let Ok(content) = read_to_string(input_path) else {
+        return;
+    };
+
+    let mut warnings = lint(spec, input_path, &content);
+    if input_path == task.input_path {
+        warnings.extend(sample_input_warning(registry, task));
+    }
+    for warning in warnings {
+        eprintln!("Warning: {}: {}", input_path, warning);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    fn messages(day: u32, path: &str, content: &str) -> Vec<String> {
+        lint(spec(day).unwrap(), path, content)
+            .iter()
+            .map(Warning::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn data_files_are_clean_test() {
+        for day in 1..=7 {
+            for path in [
+                format!("data/day{}.txt", day),
+                format!("data/day{}_test.txt", day),
+            ] {
+                let content = fs::read_to_string(&path).unwrap();
+                assert_eq!(
+                    Vec::<String>::new(),
+                    messages(day, &path, &content),
+                    "{}",
+                    path
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn line_endings_test() {
+        assert_eq!(
+            vec![
+                "CRLF line endings, convert the file with `dos2unix` or `sed -i 's/\\r$//'`",
+                "line 3: trailing blank line, some solvers read it as an empty record",
+            ],
+            messages(1, "x_test.txt", "1   2\r\n3   4\r\n\r\n")
+        );
+        assert_eq!(vec!["input is empty"], messages(1, "x_test.txt", "\n"));
+    }
+
+    #[test]
+    fn truncated_test() {
+        assert_eq!(
+            vec![
+                "no newline at the end, the download may be truncated",
+                "line 2: last line is incomplete, the download may be truncated",
+            ],
+            messages(7, "x_test.txt", "190: 10 19\n3267: 81 ")
+        );
+        assert_eq!(
+            vec![
+                "no newline at the end, the download may be truncated",
+                "line 2: last row is shorter, the download may be truncated",
+            ],
+            messages(4, "x_test.txt", "XMAS\nXM")
+        );
+    }
+
+    #[test]
+    fn shape_test() {
+        assert_eq!(
+            vec!["line 2: expected numbers separated by spaces, found \"1,2\" (1 more lines like this)"],
+            messages(2, "x_test.txt", "1 2 3\n1,2\n4;5\n")
+        );
+        assert_eq!(
+            vec!["expected 2 sections separated by a blank line, found 1"],
+            messages(5, "x_test.txt", "47|53\n75,47\n")
+        );
+        assert_eq!(
+            vec!["line 2: row is 3 wide, expected 4"],
+            messages(6, "x_test.txt", "..#.\n.^.\n....\n")
+        );
+        assert_eq!(
+            vec![
+                "line 1: unexpected character 'x'",
+                "expected 1 '^', found 0"
+            ],
+            messages(6, "x_test.txt", "..x.\n....\n")
+        );
+    }
+
+    #[test]
+    fn sample_used_as_real_test() {
+        let sample = fs::read_to_string("data/day2_test.txt").unwrap();
+        assert_eq!(
+            vec!["only 60 bytes, this looks like the sample input rather than the real one"],
+            messages(2, "inputs/alice/day2.txt", &sample)
+        );
+
+        fn noop(_: &str) -> Result<i64, Box<dyn std::error::Error>> {
+            Ok(0)
+        }
+        let registry = TaskRegistry::new(&[
+            ("day2_task1", noop, "data/day2.txt"),
+            ("day2_task2", noop, "data/day2_test.txt"),
+        ]);
+        assert_eq!(None, sample_input_warning(&registry, &registry.tasks()[0]));
+        assert_eq!(
+            Some(Warning::new(
+                "day2_task2 is registered with sample input data/day2_test.txt while \
+                 day2_task1 uses data/day2.txt"
+            )),
+            sample_input_warning(&registry, &registry.tasks()[1])
+        );
+    }
+}
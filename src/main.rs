@@ -1,52 +1,48 @@
-use std::env;
+use std::{
+    env,
+    error::Error,
+    io::{self, IsTerminal},
+    process,
+};
 
-use task_registry::TaskRegistry;
+use aoc2024::{
+    args::Args, batch, crosscheck, gen, progress, repl, runner, serve, submit,
+    task_registry::TaskRegistry, tasks_registry, trace, watch,
+};
 
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
+fn run_command(tasks_registry: &TaskRegistry) -> Result<(), Box<dyn Error>> {
+    let mut raw_args = env::args().skip(1).peekable();
 
-mod file_utils;
-mod task_registry;
-
-fn main() {
-    let tasks_registry = TaskRegistry::new(&[
-        ("day1_task1", day1::task1),
-        ("day1_task2", day1::task2),
-        ("day2_task1", day2::task1),
-        ("day2_task2", day2::task2),
-        ("day3_task1", day3::task1),
-        ("day3_task2", day3::task2),
-        ("day4_task1", day4::task1),
-        ("day4_task2", day4::task2),
-        ("day5_task1", day5::task1),
-        ("day5_task2", day5::task2),
-        ("day6_task1", day6::task1),
-        ("day6_task2", day6::task2),
-        ("day7_task1", day7::task1),
-        ("day7_task2", day7::task2),
-    ]);
-
-    let task_id = match env::args().nth(1) {
-        Some(task_id) => task_id,
-        _ => tasks_registry.latest_task_id().to_string(),
+    let command = match raw_args.peek().map(String::as_str) {
+        Some(
+            "run" | "run-all" | "bench" | "batch" | "crosscheck" | "gen" | "repl" | "serve"
+            | "submit" | "watch",
+        ) => raw_args.next().unwrap(),
+        _ => "run".to_string(),
     };
+    let args = Args::parse(raw_args, &["tests"])?;
+    trace::set_verbosity(args.verbosity());
+    progress::set_enabled(io::stdout().is_terminal());
 
-    let result = match tasks_registry.get(&task_id) {
-        Some(func) => {
-            println!("Running task_id: {}", task_id);
-            func()
-        }
-        _ => panic!("Invalid task_id: {}", task_id),
-    };
+    match command.as_str() {
+        "run-all" => runner::run_all(tasks_registry, &args),
+        "bench" => runner::bench(tasks_registry, &args),
+        "batch" => batch::run(tasks_registry, &args),
+        "crosscheck" => crosscheck::run(tasks_registry, &args),
+        "gen" => gen::run(&args),
+        "repl" => repl::run(tasks_registry, &args),
+        "serve" => serve::run(tasks_registry, &args),
+        "submit" => submit::run(tasks_registry, &args),
+        "watch" => watch::run(tasks_registry, &args),
+        _ => runner::run(tasks_registry, &args),
+    }
+}
+
+fn main() {
+    let tasks_registry = tasks_registry();
 
-    if let Ok(value) = result {
-        println!("Result: {}", value);
-    } else {
-        panic!("Error: {:?}", result.unwrap_err());
+    if let Err(err) = run_command(&tasks_registry) {
+        eprintln!("Error: {}", err);
+        process::exit(1);
     }
 }
@@ -0,0 +1,137 @@
+use std::{
+    io::{self, Write},
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+const BAR_WIDTH: usize = 30;
+const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Turns rendering on, the runner does this only when stdout is a terminal.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Progress of a long-running solver loop, rendered to stderr as a bar with ETA.
+pub struct Progress {
+    label: &'static str,
+    total: usize,
+    done: usize,
+    started: Instant,
+    last_draw: Option<Instant>,
+    enabled: bool,
+}
+
+impl Progress {
+    pub fn new(label: &'static str, total: usize) -> Self {
+        Progress {
+            label,
+            total,
+            done: 0,
+            started: Instant::now(),
+            last_draw: None,
+            enabled: ENABLED.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn inc(&mut self, items: usize) {
+        self.done = (self.done + items).min(self.total);
+
+        if !self.enabled {
+            return;
+        }
+
+        let now = Instant::now();
+        let due = self
+            .last_draw
+            .is_none_or(|last| now - last >= REDRAW_INTERVAL);
+        if due || self.done == self.total {
+            self.last_draw = Some(now);
+            self.draw();
+        }
+    }
+
+    fn draw(&self) {
+        let line = render_line(self.label, self.done, self.total, self.started.elapsed());
+        let mut stderr = io::stderr().lock();
+        let _ = write!(stderr, "\r{}", line);
+        let _ = stderr.flush();
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        if self.enabled && self.last_draw.is_some() {
+            // Wipe the bar so it does not mix with the task output.
+            let mut stderr = io::stderr().lock();
+            let _ = write!(stderr, "\r\x1b[2K");
+            let _ = stderr.flush();
+        }
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 60 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}.{}s", secs, duration.subsec_millis() / 100)
+    }
+}
+
+fn render_line(label: &str, done: usize, total: usize, elapsed: Duration) -> String {
+    let ratio = if total == 0 {
+        1.0
+    } else {
+        done as f64 / total as f64
+    };
+    let filled = (ratio * BAR_WIDTH as f64).round() as usize;
+
+    let eta = if done == 0 {
+        "?".to_string()
+    } else {
+        format_duration(elapsed.mul_f64((total - done) as f64 / done as f64))
+    };
+
+    format!(
+        "{} [{}{}] {}/{} {:3.0}% ETA {}",
+        label,
+        "#".repeat(filled),
+        "-".repeat(BAR_WIDTH - filled),
+        done,
+        total,
+        ratio * 100.0,
+        eta
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_line_test() {
+        assert_eq!(
+            "day6 [###############---------------] 50/100  50% ETA 2.0s",
+            render_line("day6", 50, 100, Duration::from_secs(2))
+        );
+        assert_eq!(
+            "day6 [------------------------------] 0/100   0% ETA ?",
+            render_line("day6", 0, 100, Duration::from_secs(2))
+        );
+        assert_eq!(
+            "day6 [########----------------------] 25/100  25% ETA 3m00s",
+            render_line("day6", 25, 100, Duration::from_secs(60))
+        );
+    }
+
+    #[test]
+    fn inc_saturates_at_total_test() {
+        let mut progress = Progress::new("test", 3);
+        progress.inc(2);
+        progress.inc(2);
+        assert_eq!(3, progress.done);
+    }
+}
@@ -0,0 +1,172 @@
+use std::{env, fmt::Debug, ops::Range};
+
+use crate::rng::Rng;
+
+const DEFAULT_SEED: u64 = 2024;
+const DEFAULT_CASES: usize = 200;
+const MAX_SHRINK_STEPS: usize = 1000;
+
+/// A value that can propose simpler versions of itself and print itself as Rust source.
+pub trait Shrink: Clone + Debug {
+    /// Candidates that are strictly simpler, most aggressive first.
+    fn shrink(&self) -> Vec<Self>;
+
+    fn fixture(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl Shrink for i64 {
+    fn shrink(&self) -> Vec<Self> {
+        let value = *self;
+        let mut candidates = Vec::new();
+
+        for candidate in [0, value / 2, value - value.signum()] {
+            if candidate.abs() < value.abs() && !candidates.contains(&candidate) {
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+}
+
+impl<T: Shrink> Shrink for Vec<T> {
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = Vec::new();
+
+        // Drop halves first, then single items, then simplify items in place.
+        if self.len() > 1 {
+            let mid = self.len() / 2;
+            candidates.push(self[..mid].to_vec());
+            candidates.push(self[mid..].to_vec());
+        }
+        for idx in 0..self.len() {
+            let mut smaller = self.clone();
+            smaller.remove(idx);
+            candidates.push(smaller);
+        }
+        for (idx, item) in self.iter().enumerate() {
+            for simpler in item.shrink() {
+                let mut candidate = self.clone();
+                candidate[idx] = simpler;
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+
+    fn fixture(&self) -> String {
+        let items: Vec<_> = self.iter().map(Shrink::fixture).collect();
+        format!("vec![{}]", items.join(", "))
+    }
+}
+
+impl<A: Shrink, B: Shrink> Shrink for (A, B) {
+    fn shrink(&self) -> Vec<Self> {
+        let left = self.0.shrink().into_iter().map(|a| (a, self.1.clone()));
+        let right = self.1.shrink().into_iter().map(|b| (self.0.clone(), b));
+        left.chain(right).collect()
+    }
+
+    fn fixture(&self) -> String {
+        format!("({}, {})", self.0.fixture(), self.1.fixture())
+    }
+}
+
+/// Vector with a length drawn from `len`, each item made by `item`.
+pub fn vec_of<T>(rng: &mut Rng, len: Range<usize>, mut item: impl FnMut(&mut Rng) -> T) -> Vec<T> {
+    let len = rng.range(len.start as i64..len.end as i64) as usize;
+    (0..len).map(|_| item(rng)).collect()
+}
+
+/// Repeatedly takes the first simpler candidate that still fails.
+fn minimise<T: Shrink>(mut value: T, property: &impl Fn(&T) -> bool) -> T {
+    for _ in 0..MAX_SHRINK_STEPS {
+        match value.shrink().into_iter().find(|c| !property(c)) {
+            Some(simpler) => value = simpler,
+            None => break,
+        }
+    }
+    value
+}
+
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Checks `property` on seeded random values and panics with a minimised, ready-to-paste
+/// fixture on failure.
+///
+/// `PROP_SEED` and `PROP_CASES` override the seed and number of cases. Properties should
+/// return `true` for inputs outside their precondition so shrinking never leaves the domain.
+pub fn check<T: Shrink>(
+    name: &str,
+    generate: impl Fn(&mut Rng) -> T,
+    property: impl Fn(&T) -> bool,
+) {
+    let seed = env_or("PROP_SEED", DEFAULT_SEED);
+    let cases = env_or("PROP_CASES", DEFAULT_CASES);
+    let mut rng = Rng::new(seed);
+
+    for case in 0..cases {
+        let value = generate(&mut rng);
+        if property(&value) {
+            continue;
+        }
+
+        let minimal = minimise(value.clone(), &property);
+        panic!(
+            "property `{}` failed on case {} (PROP_SEED={})\noriginal: {:?}\nminimised fixture:\n    {}",
+            name,
+            case,
+            seed,
+            value,
+            minimal.fixture()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic;
+
+    use super::*;
+
+    #[test]
+    fn shrink_i64_test() {
+        assert_eq!(vec![0, 5, 9], 10.shrink());
+        assert_eq!(vec![0, -1], (-2).shrink());
+        assert!(0.shrink().is_empty());
+    }
+
+    #[test]
+    fn minimise_test() {
+        // Fails whenever some item is at least 10: the minimal case is a single 10.
+        let property = |v: &Vec<i64>| v.iter().all(|&x| x < 10);
+        let minimal = minimise(vec![3, 42, -7, 15], &property);
+
+        assert_eq!(vec![10], minimal);
+        assert_eq!("vec![10]", minimal.fixture());
+    }
+
+    #[test]
+    fn check_reports_fixture_test() {
+        let result = panic::catch_unwind(|| {
+            check(
+                "sum stays small",
+                |rng| vec_of(rng, 0..10, |rng| rng.range(0..100)),
+                |v: &Vec<i64>| v.iter().sum::<i64>() < 150,
+            )
+        });
+
+        let message = *result.unwrap_err().downcast::<String>().unwrap();
+        assert!(
+            message.contains("minimised fixture:\n    vec!["),
+            "{}",
+            message
+        );
+    }
+}
@@ -0,0 +1,736 @@
+use std::{
+    error::Error,
+    io::{self, BufRead, IsTerminal, Read, Write},
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use crate::{
+    args::Args,
+    day2::{self, Malformed, ReportPolicy},
+    day3, day5, day6,
+    report::millis,
+    runner::{find_task, run_task},
+    task_registry::{day_of, Task, TaskRegistry},
+};
+
+const PROMPT_SUFFIX: &str = "> ";
+
+const COMMANDS: &[(&str, &str)] = &[
+    ("help", "help - list commands"),
+    ("tasks", "tasks - list registered tasks"),
+    ("day", "day N - switch to day N"),
+    ("sample", "sample - use data/dayN_test.txt"),
+    ("real", "real - use data/dayN.txt"),
+    (
+        "load",
+        "load PATH - use the input at PATH until the next day/sample/real",
+    ),
+    ("input", "input - show the current input"),
+    (
+        "run",
+        "run [PART|TASK_ID] - run both parts of the day, one part or a task",
+    ),
+    (
+        "inspect",
+        "inspect [NAME [ARGS]] - show parsed structures of the day",
+    ),
+    ("history", "history - show previous commands"),
+    ("quit", "quit - leave, Ctrl-D works too"),
+];
+
+type InspectFn = fn(&str, &[&str]) -> Result<String, Box<dyn Error>>;
+
+fn inspect_unsafe(path: &str, args: &[&str]) -> Result<String, Box<dyn Error>> {
+    day2::explain(path, &ReportPolicy::from_args(args)?)
+}
+
+fn inspect_stats(path: &str, args: &[&str]) -> Result<String, Box<dyn Error>> {
+    let (on_malformed, args) = match args.split_first() {
+        Some((arg, rest)) if Malformed::from_arg(arg).is_some() => {
+            (Malformed::from_arg(arg).unwrap(), rest)
+        }
+        _ => (Malformed::Collect, args),
+    };
+    Ok(day2::stats(path, &ReportPolicy::from_args(args)?, on_malformed)?.to_string())
+}
+
+fn inspect_dampen(path: &str, args: &[&str]) -> Result<String, Box<dyn Error>> {
+    let (k, args) = match args.split_first() {
+        Some((k, rest)) if k.parse::<usize>().is_ok() => (k.parse()?, rest),
+        _ => (1, args),
+    };
+    day2::explain_dampened(path, &ReportPolicy::from_args(args)?, k)
+}
+
+fn inspect_trace(path: &str, args: &[&str]) -> Result<String, Box<dyn Error>> {
+    let part = args.first().map_or(Ok(2), |part| part.parse())?;
+    day3::describe_trace(path, part)
+}
+
+fn inspect_highlight(path: &str, _: &[&str]) -> Result<String, Box<dyn Error>> {
+    day3::describe_highlighted(path)
+}
+
+fn inspect_rules(path: &str, args: &[&str]) -> Result<String, Box<dyn Error>> {
+    let page = args.first().map(|page| page.parse()).transpose()?;
+    day5::describe_rules(path, page)
+}
+
+fn inspect_obstacles(path: &str, _: &[&str]) -> Result<String, Box<dyn Error>> {
+    day6::describe_map(path)
+}
+
+/// `(day, name, usage, inspector)` for `inspect`.
+const INSPECTORS: &[(u32, &str, &str, InspectFn)] = &[
+    (
+        2,
+        "unsafe",
+        "inspect unsafe [min=N] [max=N] [increasing|decreasing] [plateaus]",
+        inspect_unsafe,
+    ),
+    (
+        2,
+        "stats",
+        "inspect stats [fail|skip|collect] [POLICY OPTIONS]",
+        inspect_stats,
+    ),
+    (
+        2,
+        "dampen",
+        "inspect dampen [K] [POLICY OPTIONS]",
+        inspect_dampen,
+    ),
+    (3, "trace", "inspect trace [PART]", inspect_trace),
+    (3, "highlight", "inspect highlight", inspect_highlight),
+    (5, "rules", "inspect rules [PAGE]", inspect_rules),
+    (6, "obstacles", "inspect obstacles", inspect_obstacles),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DataSet {
+    Sample,
+    Real,
+}
+
+#[derive(Debug, PartialEq)]
+enum Reply {
+    Output(String),
+    Quit,
+}
+
+struct Session<'a> {
+    registry: &'a TaskRegistry,
+    day: u32,
+    data: DataSet,
+    /// Set by `load`, takes precedence over `data`.
+    loaded: Option<String>,
+    history: Vec<String>,
+}
+
+impl<'a> Session<'a> {
+    fn new(registry: &'a TaskRegistry) -> Result<Self, Box<dyn Error>> {
+        Ok(Session {
+            registry,
+            day: day_of(registry.latest_task_id())?,
+            data: DataSet::Sample,
+            loaded: None,
+            history: Vec::new(),
+        })
+    }
+
+    fn prompt(&self) -> String {
+        let data = match (&self.loaded, self.data) {
+            (Some(_), _) => "loaded",
+            (None, DataSet::Sample) => "sample",
+            (None, DataSet::Real) => "real",
+        };
+        format!("day{} {}{}", self.day, data, PROMPT_SUFFIX)
+    }
+
+    fn input_path(&self) -> String {
+        match (&self.loaded, self.data) {
+            (Some(path), _) => path.clone(),
+            (None, DataSet::Sample) => format!("data/day{}_test.txt", self.day),
+            (None, DataSet::Real) => format!("data/day{}.txt", self.day),
+        }
+    }
+
+    fn day_tasks(&self) -> Vec<&'a Task> {
+        self.registry
+            .tasks()
+            .iter()
+            .filter(|t| day_of(&t.id).ok() == Some(self.day))
+            .collect()
+    }
+
+    fn execute(&mut self, line: &str) -> Result<Reply, Box<dyn Error>> {
+        let words: Vec<_> = line.split_whitespace().collect();
+        let Some((&command, args)) = words.split_first() else {
+            return Ok(Reply::Output(String::new()));
+        };
+        self.history.push(line.trim().to_string());
+
+        let output = match (command, args) {
+            ("help", []) => {
+                let lines: Vec<_> = COMMANDS.iter().map(|(_, usage)| *usage).collect();
+                lines.join("\n")
+            }
+            ("tasks", []) => {
+                let ids: Vec<_> = self
+                    .registry
+                    .tasks()
+                    .iter()
+                    .map(|t| t.id.as_str())
+                    .collect();
+                ids.join(" ")
+            }
+            ("day", [day]) => {
+                let day = day.parse()?;
+                if !self
+                    .registry
+                    .tasks()
+                    .iter()
+                    .any(|t| day_of(&t.id).ok() == Some(day))
+                {
+                    return Err(format!("no tasks for day {}", day).into());
+                }
+                self.day = day;
+                self.loaded = None;
+                format!("input {}", self.input_path())
+            }
+            ("sample", []) | ("real", []) => {
+                self.data = if command == "sample" {
+                    DataSet::Sample
+                } else {
+                    DataSet::Real
+                };
+                self.loaded = None;
+                format!("input {}", self.input_path())
+            }
+            ("load", [path]) => {
+                if !Path::new(path).is_file() {
+                    return Err(format!("no such file: {}", path).into());
+                }
+                self.loaded = Some(path.to_string());
+                format!("input {}", self.input_path())
+            }
+            ("input", []) => self.input_path(),
+            ("run", _) => self.run(args)?,
+            ("inspect", _) => self.inspect(args)?,
+            ("history", []) => {
+                let lines: Vec<_> = self
+                    .history
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, line)| format!("{:>3}  {}", idx + 1, line))
+                    .collect();
+                lines.join("\n")
+            }
+            ("quit" | "exit", []) => return Ok(Reply::Quit),
+            _ => match COMMANDS.iter().find(|(name, _)| *name == command) {
+                Some((_, usage)) => return Err(format!("usage: {}", usage).into()),
+                None => return Err(format!("unknown command {}, try help", command).into()),
+            },
+        };
+        Ok(Reply::Output(output))
+    }
+
+    fn run(&self, args: &[&str]) -> Result<String, Box<dyn Error>> {
+        let tasks = match args {
+            [] => self.day_tasks(),
+            [part] if part.parse::<u32>().is_ok() => {
+                vec![find_task(
+                    self.registry,
+                    &format!("day{}_task{}", self.day, part),
+                )?]
+            }
+            [task_id] => vec![find_task(self.registry, task_id)?],
+            _ => return Err("usage: run [PART|TASK_ID]".into()),
+        };
+
+        let input_path = self.input_path();
+        let lines: Vec<_> = tasks
+            .into_iter()
+            .map(|task| {
+                // Solvers unwrap on malformed input, which must not end the session.
+                let report = panic::catch_unwind(AssertUnwindSafe(|| {
+                    run_task(task, task.default_implementation(), &input_path, 1)
+                }));
+                match report {
+                    Ok(report) => match report.answer {
+                        Ok(answer) => format!(
+                            "{}: {} ({}ms)",
+                            task.id,
+                            answer,
+                            millis(report.timings.mean)
+                        ),
+                        Err(err) => format!("{}: error: {}", task.id, err),
+                    },
+                    Err(_) => format!("{}: solver panicked on {}", task.id, input_path),
+                }
+            })
+            .collect();
+        Ok(lines.join("\n"))
+    }
+
+    fn inspect(&self, args: &[&str]) -> Result<String, Box<dyn Error>> {
+        let available: Vec<_> = INSPECTORS
+            .iter()
+            .filter(|(day, ..)| *day == self.day)
+            .collect();
+
+        let Some((name, args)) = args.split_first() else {
+            if available.is_empty() {
+                return Ok(format!("nothing to inspect for day {}", self.day));
+            }
+            let usages: Vec<_> = available.iter().map(|(_, _, usage, _)| *usage).collect();
+            return Ok(usages.join("\n"));
+        };
+
+        let (_, _, _, inspector) = available
+            .iter()
+            .find(|(_, n, ..)| n == name)
+            .ok_or_else(|| format!("day {} has no inspector {}", self.day, name))?;
+        inspector(&self.input_path(), args)
+    }
+
+    /// Candidates for the word under the cursor, which is the last word of `line`.
+    fn completions(&self, line: &str) -> Vec<String> {
+        let words: Vec<_> = line.split_whitespace().collect();
+        let completing_new_word = line.is_empty() || line.ends_with(' ');
+        let position = if completing_new_word {
+            words.len()
+        } else {
+            words.len() - 1
+        };
+        let prefix = if completing_new_word {
+            ""
+        } else {
+            words[position]
+        };
+
+        let candidates: Vec<String> = match (position, words.first()) {
+            (0, _) => COMMANDS.iter().map(|(name, _)| name.to_string()).collect(),
+            (1, Some(&"run")) => self.registry.tasks().iter().map(|t| t.id.clone()).collect(),
+            (1, Some(&"inspect")) => INSPECTORS
+                .iter()
+                .filter(|(day, ..)| *day == self.day)
+                .map(|(_, name, ..)| name.to_string())
+                .collect(),
+            (1, Some(&"day")) => {
+                let mut days: Vec<_> = self
+                    .registry
+                    .tasks()
+                    .iter()
+                    .filter_map(|t| day_of(&t.id).ok())
+                    .map(|day| day.to_string())
+                    .collect();
+                days.dedup();
+                days
+            }
+            _ => Vec::new(),
+        };
+
+        candidates
+            .into_iter()
+            .filter(|c| c.starts_with(prefix))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Key {
+    Char(char),
+    Backspace,
+    Enter,
+    Tab,
+    Up,
+    Down,
+    Interrupt,
+    Eof,
+    Ignored,
+}
+
+fn read_byte(input: &mut impl Read) -> io::Result<Option<u8>> {
+    let mut byte = [0];
+    match input.read(&mut byte)? {
+        0 => Ok(None),
+        _ => Ok(Some(byte[0])),
+    }
+}
+
+/// Decodes one key press from a terminal in raw mode.
+fn read_key(input: &mut impl Read) -> io::Result<Key> {
+    let Some(byte) = read_byte(input)? else {
+        return Ok(Key::Eof);
+    };
+
+    let key = match byte {
+        b'\r' | b'\n' => Key::Enter,
+        b'\t' => Key::Tab,
+        3 => Key::Interrupt,
+        4 => Key::Eof,
+        8 | 127 => Key::Backspace,
+        0x1b => match (read_byte(input)?, read_byte(input)?) {
+            (Some(b'['), Some(b'A')) => Key::Up,
+            (Some(b'['), Some(b'B')) => Key::Down,
+            _ => Key::Ignored,
+        },
+        byte if byte.is_ascii_control() => Key::Ignored,
+        byte if byte.is_ascii() => Key::Char(byte as char),
+        first => {
+            let len = match first {
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                _ => 4,
+            };
+            let mut bytes = vec![first];
+            for _ in 1..len {
+                bytes.extend(read_byte(input)?);
+            }
+            match std::str::from_utf8(&bytes)
+                .ok()
+                .and_then(|s| s.chars().next())
+            {
+                Some(c) => Key::Char(c),
+                None => Key::Ignored,
+            }
+        }
+    };
+    Ok(key)
+}
+
+#[derive(Debug, PartialEq)]
+enum Edit {
+    Continue,
+    Submit(String),
+    ShowCandidates(Vec<String>),
+    Cancel,
+    Eof,
+}
+
+/// Line being typed, with the position in the history when browsing it with arrows.
+struct LineState {
+    line: String,
+    history_idx: usize,
+}
+
+impl LineState {
+    fn new(history: &[String]) -> Self {
+        LineState {
+            line: String::new(),
+            history_idx: history.len(),
+        }
+    }
+
+    fn apply(&mut self, key: Key, history: &[String], candidates: &[String]) -> Edit {
+        match key {
+            Key::Char(c) => self.line.push(c),
+            Key::Backspace => {
+                self.line.pop();
+            }
+            Key::Enter => return Edit::Submit(self.line.clone()),
+            Key::Interrupt => return Edit::Cancel,
+            Key::Eof if self.line.is_empty() => return Edit::Eof,
+            Key::Up if self.history_idx > 0 => {
+                self.history_idx -= 1;
+                self.line = history[self.history_idx].clone();
+            }
+            Key::Down if self.history_idx < history.len() => {
+                self.history_idx += 1;
+                self.line = history.get(self.history_idx).cloned().unwrap_or_default();
+            }
+            Key::Tab => return self.complete(candidates),
+            _ => (),
+        }
+        Edit::Continue
+    }
+
+    /// Extends the last word by the prefix shared by all candidates.
+    fn complete(&mut self, candidates: &[String]) -> Edit {
+        let word_start = self.line.rfind(' ').map_or(0, |idx| idx + 1);
+        let word = &self.line[word_start..];
+
+        let Some(first) = candidates.first() else {
+            return Edit::Continue;
+        };
+        let mut common = first.as_str();
+        for candidate in &candidates[1..] {
+            let shared = common
+                .char_indices()
+                .zip(candidate.chars())
+                .find(|((_, a), b)| a != b)
+                .map_or(common.len().min(candidate.len()), |((idx, _), _)| idx);
+            common = &common[..shared];
+        }
+
+        if candidates.len() == 1 {
+            self.line = format!("{}{} ", &self.line[..word_start], common);
+            Edit::Continue
+        } else if common.len() > word.len() {
+            self.line = format!("{}{}", &self.line[..word_start], common);
+            Edit::Continue
+        } else {
+            Edit::ShowCandidates(candidates.to_vec())
+        }
+    }
+}
+
+/// Puts the terminal in raw mode with `stty` and restores the previous mode on drop.
+struct RawMode {
+    saved: String,
+}
+
+impl RawMode {
+    fn enable() -> Option<Self> {
+        let output = Command::new("stty")
+            .arg("-g")
+            .stdin(Stdio::inherit())
+            .output()
+            .ok()
+            .filter(|o| o.status.success())?;
+        let saved = String::from_utf8(output.stdout).ok()?.trim().to_string();
+
+        let status = Command::new("stty")
+            .args(["raw", "-echo"])
+            .stdin(Stdio::inherit())
+            .status()
+            .ok()?;
+        status.success().then_some(RawMode { saved })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = Command::new("stty")
+            .arg(&self.saved)
+            .stdin(Stdio::inherit())
+            .status();
+    }
+}
+
+/// Reads a line with history and tab completion, `None` on end of input. Expects raw mode.
+fn read_line_raw(session: &Session) -> io::Result<Option<String>> {
+    let mut stdin = io::stdin().lock();
+    let mut out = io::stdout().lock();
+    let mut state = LineState::new(&session.history);
+
+    loop {
+        write!(out, "\r\x1b[K{}{}", session.prompt(), state.line)?;
+        out.flush()?;
+
+        let key = read_key(&mut stdin)?;
+        let candidates = match key {
+            Key::Tab => session.completions(&state.line),
+            _ => Vec::new(),
+        };
+
+        match state.apply(key, &session.history, &candidates) {
+            Edit::Continue => (),
+            Edit::Submit(line) => {
+                write!(out, "\r\n")?;
+                return Ok(Some(line));
+            }
+            Edit::ShowCandidates(candidates) => write!(out, "\r\n{}\r\n", candidates.join("  "))?,
+            Edit::Cancel => {
+                write!(out, "^C\r\n")?;
+                state = LineState::new(&session.history);
+            }
+            Edit::Eof => {
+                write!(out, "\r\n")?;
+                return Ok(None);
+            }
+        }
+    }
+}
+
+fn read_line_plain(session: &Session, interactive: bool) -> io::Result<Option<String>> {
+    if interactive {
+        print!("{}", session.prompt());
+        io::stdout().flush()?;
+    }
+    let mut line = String::new();
+    match io::stdin().lock().read_line(&mut line)? {
+        0 => Ok(None),
+        _ => Ok(Some(line.trim_end().to_string())),
+    }
+}
+
+/// `repl` - interactive session to load inputs, run parts and inspect parsed structures.
+/// Reads commands line by line when stdin is not a terminal, so it can be scripted.
+pub fn run(registry: &TaskRegistry, _args: &Args) -> Result<(), Box<dyn Error>> {
+    let mut session = Session::new(registry)?;
+    let interactive = io::stdin().is_terminal();
+    if interactive {
+        println!("Type help for commands, Tab completes, Up/Down browse history.");
+    }
+
+    loop {
+        let line = match interactive.then(RawMode::enable).flatten() {
+            Some(_raw) => read_line_raw(&session)?,
+            None => read_line_plain(&session, interactive)?,
+        };
+        let Some(line) = line else {
+            return Ok(());
+        };
+
+        match session.execute(&line) {
+            Ok(Reply::Output(output)) if output.is_empty() => (),
+            Ok(Reply::Output(output)) => println!("{}", output),
+            Ok(Reply::Quit) => return Ok(()),
+            Err(err) => println!("Error: {}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use crate::tasks_registry;
+
+    use super::*;
+
+    fn output(session: &mut Session, line: &str) -> String {
+        match session.execute(line).unwrap() {
+            Reply::Output(output) => output,
+            Reply::Quit => panic!("unexpected quit"),
+        }
+    }
+
+    #[test]
+    fn execute_test() {
+        let registry = tasks_registry();
+        let mut session = Session::new(&registry).unwrap();
+
+        assert_eq!("input data/day5_test.txt", output(&mut session, "day 5"));
+        assert!(output(&mut session, "run 1").starts_with("day5_task1: 143 ("));
+        assert!(output(&mut session, "inspect rules 29").starts_with("29 comes after [47"));
+        assert_eq!("input data/day5.txt", output(&mut session, "real"));
+        assert_eq!("day5 real> ", session.prompt());
+
+        assert_eq!("input data/day1.txt", output(&mut session, "day 1"));
+        assert_eq!(
+            "nothing to inspect for day 1",
+            output(&mut session, "inspect")
+        );
+        assert!(session.execute("inspect rules").is_err());
+
+        output(&mut session, "day 2");
+        output(&mut session, "sample");
+        assert!(output(&mut session, "inspect unsafe max=4").ends_with("3 of 6 reports unsafe"));
+        assert!(session.execute("inspect unsafe sideways").is_err());
+        assert_eq!(
+            "safe: 2\ndampened safe: 2\nunsafe: 2\nmalformed: 0",
+            output(&mut session, "inspect stats skip")
+        );
+        assert!(output(&mut session, "inspect dampen 2")
+            .ends_with("6 of 6 reports safe with up to 2 removed"));
+        assert!(output(&mut session, "inspect dampen increasing")
+            .ends_with("2 of 6 reports safe with up to 1 removed"));
+        assert!(session.execute("day 42").is_err());
+        assert!(session.execute("load data/missing.txt").is_err());
+        assert!(session.execute("bogus").is_err());
+
+        assert!(output(&mut session, "history").starts_with("  1  day 5\n  2  run 1"));
+        assert_eq!(Reply::Quit, session.execute("quit").unwrap());
+    }
+
+    #[test]
+    fn run_survives_panicking_solver_test() {
+        let registry = tasks_registry();
+        let mut session = Session::new(&registry).unwrap();
+
+        output(&mut session, "day 2");
+        output(&mut session, "load data/day5_test.txt");
+        assert_eq!(
+            "day2_task1: error: line 1: \"47|53\" is not a level: invalid digit found in string",
+            output(&mut session, "run 1")
+        );
+
+        // An equation without numbers still panics day 7.
+        let path = env::temp_dir().join(format!("aoc2024_repl_{}.txt", std::process::id()));
+        fs::write(&path, "5: \n").unwrap();
+        output(&mut session, "day 7");
+        output(&mut session, &format!("load {}", path.display()));
+        assert!(output(&mut session, "run 1").contains("solver panicked"));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn completions_test() {
+        let registry = tasks_registry();
+        let mut session = Session::new(&registry).unwrap();
+        output(&mut session, "day 6");
+
+        assert_eq!(vec!["real", "run"], session.completions("r"));
+        assert_eq!(
+            vec!["day6_task1", "day6_task2"],
+            session.completions("run day6")
+        );
+        assert_eq!(vec!["obstacles"], session.completions("inspect "));
+        assert_eq!(
+            vec!["1", "2", "3", "4", "5", "6", "7"],
+            session.completions("day ")
+        );
+        assert!(session.completions("quit now ").is_empty());
+    }
+
+    #[test]
+    fn read_key_test() {
+        let mut input: &[u8] = b"a\x1b[A\x7f\t\r\x03\xc3\xa9";
+        let keys: Vec<_> = (0..8).map(|_| read_key(&mut input).unwrap()).collect();
+
+        assert_eq!(
+            vec![
+                Key::Char('a'),
+                Key::Up,
+                Key::Backspace,
+                Key::Tab,
+                Key::Enter,
+                Key::Interrupt,
+                Key::Char('é'),
+                Key::Eof,
+            ],
+            keys
+        );
+    }
+
+    #[test]
+    fn line_state_test() {
+        let history = vec!["run 1".to_string(), "day 5".to_string()];
+        let mut state = LineState::new(&history);
+
+        assert_eq!(Edit::Continue, state.apply(Key::Up, &history, &[]));
+        assert_eq!("day 5", state.line);
+        state.apply(Key::Up, &history, &[]);
+        state.apply(Key::Up, &history, &[]);
+        assert_eq!("run 1", state.line);
+        state.apply(Key::Down, &history, &[]);
+        state.apply(Key::Down, &history, &[]);
+        assert_eq!("", state.line);
+
+        state.apply(Key::Char('r'), &history, &[]);
+        let candidates = vec!["real".to_string(), "run".to_string()];
+        assert_eq!(
+            Edit::ShowCandidates(candidates.clone()),
+            state.apply(Key::Tab, &history, &candidates)
+        );
+
+        state.apply(Key::Char('u'), &history, &[]);
+        state.apply(Key::Tab, &history, &["run".to_string()]);
+        assert_eq!("run ", state.line);
+
+        let candidates = vec!["day6_task1".to_string(), "day6_task2".to_string()];
+        state.apply(Key::Tab, &history, &candidates);
+        assert_eq!("run day6_task", state.line);
+
+        assert_eq!(
+            Edit::Submit("run day6_task".to_string()),
+            state.apply(Key::Enter, &history, &[])
+        );
+        assert_eq!(Edit::Cancel, state.apply(Key::Interrupt, &history, &[]));
+    }
+}
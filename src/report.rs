@@ -0,0 +1,351 @@
+use std::{error::Error, fmt, io::Write, str::FromStr, time::Duration};
+
+use crate::{
+    alloc_stats::{format_bytes, AllocStats},
+    runner::TaskReport,
+    task_registry::DEFAULT_IMPLEMENTATION,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl FromStr for Format {
+    type Err = FormatParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "markdown" | "md" => Ok(Format::Markdown),
+            _ => Err(FormatParseError(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FormatParseError(String);
+
+impl fmt::Display for FormatParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown format {:?}, expected text, json, csv or markdown",
+            self.0
+        )
+    }
+}
+
+impl Error for FormatParseError {}
+
+pub fn millis(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1000.0)
+}
+
+pub fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn markdown_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Allocation columns as raw numbers, `missing` when the counting allocator is off.
+fn alloc_fields(allocs: Option<AllocStats>, missing: &str) -> [String; 3] {
+    match allocs {
+        Some(stats) => [
+            stats.allocations.to_string(),
+            stats.bytes_allocated.to_string(),
+            stats.peak_bytes.to_string(),
+        ],
+        None => [
+            missing.to_string(),
+            missing.to_string(),
+            missing.to_string(),
+        ],
+    }
+}
+
+fn render_text(reports: &[TaskReport], out: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+    for report in reports {
+        if report.implementation == DEFAULT_IMPLEMENTATION {
+            writeln!(out, "Running task_id: {}", report.task_id)?;
+        } else {
+            writeln!(
+                out,
+                "Running task_id: {} ({})",
+                report.task_id, report.implementation
+            )?;
+        }
+        match &report.answer {
+            Ok(answer) => writeln!(out, "Result: {}", answer)?,
+            Err(err) => writeln!(out, "Error: {}", err)?,
+        }
+
+        let timings = &report.timings;
+        if timings.runs > 1 {
+            writeln!(
+                out,
+                "Time: mean {}ms, min {}ms, max {}ms over {} runs",
+                millis(timings.mean),
+                millis(timings.min),
+                millis(timings.max),
+                timings.runs
+            )?;
+        } else {
+            writeln!(out, "Time: {}ms", millis(timings.mean))?;
+        }
+
+        if let Some(stats) = report.allocs {
+            writeln!(
+                out,
+                "Memory: {} allocations, {} allocated, {} peak",
+                stats.allocations,
+                format_bytes(stats.bytes_allocated),
+                format_bytes(stats.peak_bytes as u64)
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn render_json(reports: &[TaskReport], out: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+    writeln!(out, "[")?;
+    for (idx, report) in reports.iter().enumerate() {
+        let (answer, error) = match &report.answer {
+            Ok(answer) => (answer.to_string(), "null".to_string()),
+            Err(err) => ("null".to_string(), json_string(err)),
+        };
+        let [allocations, bytes_allocated, peak_bytes] = alloc_fields(report.allocs, "null");
+        let separator = if idx + 1 < reports.len() { "," } else { "" };
+
+        writeln!(
+            out,
+            "  {{\"task_id\": {}, \"implementation\": {}, \"input_file\": {}, \"answer\": {}, \
+             \"error\": {}, \"runs\": {}, \"min_ms\": {}, \"mean_ms\": {}, \"max_ms\": {}, \
+             \"allocations\": {}, \"bytes_allocated\": {}, \"peak_bytes\": {}}}{}",
+            json_string(&report.task_id),
+            json_string(&report.implementation),
+            json_string(&report.input_file),
+            answer,
+            error,
+            report.timings.runs,
+            millis(report.timings.min),
+            millis(report.timings.mean),
+            millis(report.timings.max),
+            allocations,
+            bytes_allocated,
+            peak_bytes,
+            separator
+        )?;
+    }
+    writeln!(out, "]")?;
+    Ok(())
+}
+
+fn render_csv(reports: &[TaskReport], out: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+    writeln!(
+        out,
+        "task_id,implementation,input_file,answer,error,runs,min_ms,mean_ms,max_ms,\
+         allocations,bytes_allocated,peak_bytes"
+    )?;
+    for report in reports {
+        let (answer, error) = match &report.answer {
+            Ok(answer) => (answer.to_string(), String::new()),
+            Err(err) => (String::new(), csv_field(err)),
+        };
+        let [allocations, bytes_allocated, peak_bytes] = alloc_fields(report.allocs, "");
+
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{},{},{},{},{},{}",
+            csv_field(&report.task_id),
+            csv_field(&report.implementation),
+            csv_field(&report.input_file),
+            answer,
+            error,
+            report.timings.runs,
+            millis(report.timings.min),
+            millis(report.timings.mean),
+            millis(report.timings.max),
+            allocations,
+            bytes_allocated,
+            peak_bytes,
+        )?;
+    }
+    Ok(())
+}
+
+fn render_markdown(reports: &[TaskReport], out: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+    writeln!(
+        out,
+        "| Task | Implementation | Input | Answer | Runs | Mean (ms) | Min (ms) | Max (ms) | Allocs | Allocated | Peak |"
+    )?;
+    writeln!(out, "|---|---|---|---:|---:|---:|---:|---:|---:|---:|---:|")?;
+    for report in reports {
+        let answer = match &report.answer {
+            Ok(answer) => answer.to_string(),
+            Err(err) => format!("error: {}", markdown_cell(err)),
+        };
+        let [allocations, bytes_allocated, peak_bytes] = match report.allocs {
+            Some(stats) => [
+                stats.allocations.to_string(),
+                format_bytes(stats.bytes_allocated),
+                format_bytes(stats.peak_bytes as u64),
+            ],
+            None => ["-".to_string(), "-".to_string(), "-".to_string()],
+        };
+
+        writeln!(
+            out,
+            "| {} | {} | {} | {} | {} | {} | {} | {} | {} | {} | {} |",
+            markdown_cell(&report.task_id),
+            markdown_cell(&report.implementation),
+            markdown_cell(&report.input_file),
+            answer,
+            report.timings.runs,
+            millis(report.timings.mean),
+            millis(report.timings.min),
+            millis(report.timings.max),
+            allocations,
+            bytes_allocated,
+            peak_bytes,
+        )?;
+    }
+    Ok(())
+}
+
+pub fn render(
+    reports: &[TaskReport],
+    format: Format,
+    out: &mut dyn Write,
+) -> Result<(), Box<dyn Error>> {
+    match format {
+        Format::Text => render_text(reports, out),
+        Format::Json => render_json(reports, out),
+        Format::Csv => render_csv(reports, out),
+        Format::Markdown => render_markdown(reports, out),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runner::Timings;
+
+    use super::*;
+
+    fn reports() -> Vec<TaskReport> {
+        let timings = Timings {
+            runs: 1,
+            min: Duration::from_micros(1500),
+            mean: Duration::from_micros(1500),
+            max: Duration::from_micros(1500),
+        };
+
+        vec![
+            TaskReport {
+                task_id: "day1_task1".to_string(),
+                implementation: DEFAULT_IMPLEMENTATION.to_string(),
+                input_file: "data/day1.txt".to_string(),
+                answer: Ok(11),
+                timings,
+                allocs: Some(AllocStats {
+                    allocations: 3,
+                    bytes_allocated: 2048,
+                    peak_bytes: 1024,
+                }),
+            },
+            TaskReport {
+                task_id: "day1_task2".to_string(),
+                implementation: "fast".to_string(),
+                input_file: "data/day1.txt".to_string(),
+                answer: Err("No such file, \"x\"".to_string()),
+                timings,
+                allocs: None,
+            },
+        ]
+    }
+
+    fn render_to_string(format: Format) -> String {
+        let mut out = Vec::new();
+        render(&reports(), format, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn render_json_test() {
+        assert_eq!(
+            "[\n  \
+             {\"task_id\": \"day1_task1\", \"implementation\": \"default\", \"input_file\": \"data/day1.txt\", \
+             \"answer\": 11, \"error\": null, \
+             \"runs\": 1, \"min_ms\": 1.500, \"mean_ms\": 1.500, \"max_ms\": 1.500, \
+             \"allocations\": 3, \"bytes_allocated\": 2048, \"peak_bytes\": 1024},\n  \
+             {\"task_id\": \"day1_task2\", \"implementation\": \"fast\", \"input_file\": \"data/day1.txt\", \
+             \"answer\": null, \
+             \"error\": \"No such file, \\\"x\\\"\", \"runs\": 1, \"min_ms\": 1.500, \"mean_ms\": 1.500, \"max_ms\": 1.500, \
+             \"allocations\": null, \"bytes_allocated\": null, \"peak_bytes\": null}\n\
+             ]\n",
+            render_to_string(Format::Json)
+        );
+    }
+
+    #[test]
+    fn render_csv_test() {
+        assert_eq!(
+            "task_id,implementation,input_file,answer,error,runs,min_ms,mean_ms,max_ms,\
+             allocations,bytes_allocated,peak_bytes\n\
+             day1_task1,default,data/day1.txt,11,,1,1.500,1.500,1.500,3,2048,1024\n\
+             day1_task2,fast,data/day1.txt,,\"No such file, \"\"x\"\"\",1,1.500,1.500,1.500,,,\n",
+            render_to_string(Format::Csv)
+        );
+    }
+
+    #[test]
+    fn render_markdown_test() {
+        let rendered = render_to_string(Format::Markdown);
+        assert!(rendered.contains(
+            "| day1_task1 | default | data/day1.txt | 11 | 1 | 1.500 | 1.500 | 1.500 | 3 | 2.0KiB | 1.0KiB |"
+        ));
+        assert!(rendered.contains("| error: No such file, \"x\" |"));
+    }
+
+    #[test]
+    fn render_text_test() {
+        let rendered = render_to_string(Format::Text);
+        assert!(rendered.starts_with("Running task_id: day1_task1\nResult: 11\n"));
+        assert!(rendered.contains("Running task_id: day1_task2 (fast)\nError: No such file"));
+    }
+
+    #[test]
+    fn format_from_str_test() {
+        assert_eq!(Format::Markdown, "md".parse().unwrap());
+        assert!("yaml".parse::<Format>().is_err());
+    }
+}
@@ -0,0 +1,227 @@
+use std::{
+    error::Error,
+    io,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    alloc_stats::{self, AllocStats},
+    args::Args,
+    lint,
+    report::{self, Format},
+    task_registry::{Implementation, Task, TaskRegistry},
+    trace,
+};
+
+const DEFAULT_BENCH_ITERATIONS: u32 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Timings {
+    pub runs: u32,
+    pub min: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+}
+
+impl Timings {
+    fn from_samples(samples: &[Duration]) -> Self {
+        let total: Duration = samples.iter().sum();
+        Timings {
+            runs: samples.len().try_into().unwrap(),
+            min: samples.iter().min().copied().unwrap_or_default(),
+            mean: total / samples.len().max(1).try_into().unwrap(),
+            max: samples.iter().max().copied().unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskReport {
+    pub task_id: String,
+    pub implementation: String,
+    pub input_file: String,
+    pub answer: Result<i64, String>,
+    pub timings: Timings,
+    /// Allocations of the last run, only with the `alloc-stats` feature.
+    pub allocs: Option<AllocStats>,
+}
+
+/// Runs the implementation of the task `iterations` times, stopping at the first error.
+pub fn run_task(
+    task: &Task,
+    implementation: &Implementation,
+    input_path: &str,
+    iterations: u32,
+) -> TaskReport {
+    let mut samples = Vec::new();
+    let mut answer = Err("task was not run".to_string());
+    let mut allocs = None;
+    trace::info!(
+        "running {} ({}) on {}",
+        task.id,
+        implementation.name,
+        input_path
+    );
+
+    for _ in 0..iterations.max(1) {
+        let start = Instant::now();
+        (answer, allocs) = alloc_stats::measure(|| {
+            (implementation.func)(input_path).map_err(|err| err.to_string())
+        });
+        samples.push(start.elapsed());
+
+        if answer.is_err() {
+            break;
+        }
+    }
+
+    TaskReport {
+        task_id: task.id.clone(),
+        implementation: implementation.name.clone(),
+        input_file: input_path.to_string(),
+        answer,
+        timings: Timings::from_samples(&samples),
+        allocs,
+    }
+}
+
+fn print_reports(reports: &[TaskReport], args: &Args) -> Result<(), Box<dyn Error>> {
+    let format: Format = args.option_or("format", Format::Text)?;
+    report::render(reports, format, &mut io::stdout().lock())?;
+
+    let failed = reports.iter().filter(|r| r.answer.is_err()).count();
+    if failed > 0 {
+        return Err(format!("{} task(s) failed", failed).into());
+    }
+    Ok(())
+}
+
+pub fn find_task<'a>(
+    registry: &'a TaskRegistry,
+    task_id: &str,
+) -> Result<&'a Task, Box<dyn Error>> {
+    registry
+        .get(task_id)
+        .ok_or_else(|| format!("Invalid task_id: {}", task_id).into())
+}
+
+/// Implementations picked with `--impl NAME`, `--impl all` selects every one of them.
+fn selected_implementations<'a>(
+    task: &'a Task,
+    args: &Args,
+) -> Result<Vec<&'a Implementation>, Box<dyn Error>> {
+    match args.option("impl") {
+        Some("all") => Ok(task.implementations().iter().collect()),
+        Some(name) => {
+            let implementation = task
+                .implementation(name)
+                .ok_or_else(|| format!("{} has no implementation {}", task.id, name))?;
+            Ok(vec![implementation])
+        }
+        None => Ok(vec![task.default_implementation()]),
+    }
+}
+
+/// `run [task_id] [--impl NAME] [--input PATH]` - runs a single task, the latest one by default.
+pub fn run(registry: &TaskRegistry, args: &Args) -> Result<(), Box<dyn Error>> {
+    let task_id = match args.positional().first() {
+        Some(task_id) => task_id.as_str(),
+        None => registry.latest_task_id(),
+    };
+    let task = find_task(registry, task_id)?;
+    let input_path = args.option("input").unwrap_or(&task.input_path);
+    lint::warn_about_input(registry, task, input_path);
+
+    let reports: Vec<_> = selected_implementations(task, args)?
+        .into_iter()
+        .map(|implementation| run_task(task, implementation, input_path, 1))
+        .collect();
+    print_reports(&reports, args)
+}
+
+/// `run-all` - runs every registered task once.
+pub fn run_all(registry: &TaskRegistry, args: &Args) -> Result<(), Box<dyn Error>> {
+    let reports: Vec<_> = registry
+        .tasks()
+        .iter()
+        .map(|task| {
+            lint::warn_about_input(registry, task, &task.input_path);
+            run_task(task, task.default_implementation(), &task.input_path, 1)
+        })
+        .collect();
+    print_reports(&reports, args)
+}
+
+/// `bench [task_id...] [--iterations N] [--impl NAME|all] [--input PATH]` - times tasks over
+/// several runs, all tasks by default. Pair with `gen` to bench on large synthetic inputs.
+pub fn bench(registry: &TaskRegistry, args: &Args) -> Result<(), Box<dyn Error>> {
+    let iterations = args.option_or("iterations", DEFAULT_BENCH_ITERATIONS)?;
+
+    let tasks = if args.positional().is_empty() {
+        registry.tasks().iter().collect()
+    } else {
+        args.positional()
+            .iter()
+            .map(|task_id| find_task(registry, task_id))
+            .collect::<Result<Vec<_>, _>>()?
+    };
+
+    let mut reports = Vec::new();
+    for task in tasks {
+        let input_path = args.option("input").unwrap_or(&task.input_path);
+        lint::warn_about_input(registry, task, input_path);
+        for implementation in selected_implementations(task, args)? {
+            reports.push(run_task(task, implementation, input_path, iterations));
+        }
+    }
+    print_reports(&reports, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::task_registry::DEFAULT_IMPLEMENTATION;
+
+    use super::*;
+
+    fn ok_task(_: &str) -> Result<i64, Box<dyn Error>> {
+        Ok(42)
+    }
+
+    fn failing_task(_: &str) -> Result<i64, Box<dyn Error>> {
+        Err("boom".into())
+    }
+
+    fn fast_task(_: &str) -> Result<i64, Box<dyn Error>> {
+        Ok(42)
+    }
+
+    #[test]
+    fn run_task_test() {
+        let registry = TaskRegistry::new(&[
+            ("ok", ok_task, "data/ok.txt"),
+            ("failing", failing_task, "data/failing.txt"),
+        ])
+        .with_implementations(&[("ok", "fast", fast_task)]);
+
+        let task = registry.get("ok").unwrap();
+        let report = run_task(task, task.default_implementation(), &task.input_path, 3);
+        assert_eq!(Ok(42), report.answer);
+        assert_eq!(DEFAULT_IMPLEMENTATION, report.implementation);
+        assert_eq!("data/ok.txt", report.input_file);
+        assert_eq!(3, report.timings.runs);
+
+        let report = run_task(
+            task,
+            task.implementation("fast").unwrap(),
+            "data/big.txt",
+            1,
+        );
+        assert_eq!("fast", report.implementation);
+        assert_eq!("data/big.txt", report.input_file);
+
+        let task = registry.get("failing").unwrap();
+        let report = run_task(task, task.default_implementation(), &task.input_path, 3);
+        assert_eq!(Err("boom".to_string()), report.answer);
+        assert_eq!(1, report.timings.runs);
+    }
+}
@@ -0,0 +1,388 @@
+use std::{
+    error::Error,
+    fmt,
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    panic::{self, AssertUnwindSafe},
+    time::Duration,
+};
+
+use crate::{
+    args::Args,
+    file_utils::with_memory_input,
+    report::{json_string, millis},
+    runner::run_task,
+    task_registry::TaskRegistry,
+    trace,
+};
+
+const DEFAULT_PORT: u16 = 8024;
+const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+/// How long a client may stall reading or writing before its connection is dropped, so one
+/// idle client cannot block the others.
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug)]
+struct BodyTooLarge(usize);
+
+impl fmt::Display for BodyTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "body of {} bytes is over the {} byte limit",
+            self.0, MAX_BODY_BYTES
+        )
+    }
+}
+
+impl Error for BodyTooLarge {}
+
+#[derive(Debug, PartialEq)]
+struct Request {
+    method: String,
+    path: String,
+    query: Option<String>,
+    body: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn json(status: u16, body: String) -> Self {
+        Response { status, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Response::json(status, format!("{{\"error\": {}}}", json_string(message)))
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            408 => "Request Timeout",
+            413 => "Payload Too Large",
+            422 => "Unprocessable Entity",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+fn read_request(reader: &mut impl BufRead) -> Result<Request, Box<dyn Error>> {
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().ok_or("empty request")?.to_string();
+    let target = parts.next().ok_or("missing request target")?;
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path.to_string(), Some(query.to_string())),
+        None => (target.to_string(), None),
+    };
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse()?;
+            }
+        }
+    }
+
+    if content_length > MAX_BODY_BYTES {
+        return Err(BodyTooLarge(content_length).into());
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    Ok(Request {
+        method,
+        path,
+        query,
+        body,
+    })
+}
+
+fn write_response(out: &mut impl Write, response: &Response) -> Result<(), Box<dyn Error>> {
+    write!(
+        out,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        response.status,
+        response.reason(),
+        response.body.len(),
+        response.body
+    )?;
+    out.flush()?;
+    Ok(())
+}
+
+fn list_tasks(registry: &TaskRegistry) -> Response {
+    let tasks: Vec<_> = registry
+        .tasks()
+        .iter()
+        .map(|task| {
+            let implementations: Vec<_> = task
+                .implementations()
+                .iter()
+                .map(|i| json_string(&i.name))
+                .collect();
+            format!(
+                "{{\"task_id\": {}, \"input_file\": {}, \"implementations\": [{}]}}",
+                json_string(&task.id),
+                json_string(&task.input_path),
+                implementations.join(", ")
+            )
+        })
+        .collect();
+    Response::json(200, format!("[{}]", tasks.join(", ")))
+}
+
+fn query_param<'a>(query: Option<&'a str>, name: &str) -> Option<&'a str> {
+    query?
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+fn solve(registry: &TaskRegistry, day: &str, part: &str, request: &Request) -> Response {
+    let task_id = format!("day{}_task{}", day, part);
+    let Some(task) = registry.get(&task_id) else {
+        return Response::error(404, &format!("unknown task {}", task_id));
+    };
+    let implementation = match query_param(request.query.as_deref(), "impl") {
+        Some(name) => match task.implementation(name) {
+            Some(implementation) => implementation,
+            None => {
+                return Response::error(404, &format!("{} has no implementation {}", task_id, name))
+            }
+        },
+        None => task.default_implementation(),
+    };
+    let Ok(input) = std::str::from_utf8(&request.body) else {
+        return Response::error(400, "input is not valid UTF-8");
+    };
+
+    let report = panic::catch_unwind(AssertUnwindSafe(|| {
+        with_memory_input(input, |path| run_task(task, implementation, path, 1))
+    }));
+
+    match report {
+        Ok(report) => match report.answer {
+            Ok(answer) => Response::json(
+                200,
+                format!(
+                    "{{\"task_id\": {}, \"implementation\": {}, \"answer\": {}, \"time_ms\": {}}}",
+                    json_string(&report.task_id),
+                    json_string(&report.implementation),
+                    answer,
+                    millis(report.timings.mean)
+                ),
+            ),
+            Err(err) => Response::error(422, &err),
+        },
+        Err(_) => Response::error(422, "solver panicked, is the input valid?"),
+    }
+}
+
+fn handle(registry: &TaskRegistry, request: &Request) -> Response {
+    let segments: Vec<_> = request.path.split('/').filter(|s| !s.is_empty()).collect();
+
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["tasks"]) => list_tasks(registry),
+        ("POST", ["solve", day, part]) => solve(registry, day, part, request),
+        (_, ["tasks"] | ["solve", _, _]) => Response::error(405, "method not allowed"),
+        _ => Response::error(404, "not found"),
+    }
+}
+
+/// Whether reading failed because the client stopped sending before the timeout.
+fn is_timeout(err: &(dyn Error + 'static)) -> bool {
+    err.downcast_ref::<io::Error>().is_some_and(|err| {
+        matches!(
+            err.kind(),
+            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+        )
+    })
+}
+
+fn serve_connection(
+    registry: &TaskRegistry,
+    stream: TcpStream,
+    timeout: Duration,
+) -> Result<(), Box<dyn Error>> {
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+
+    let mut reader = BufReader::new(&stream);
+    let response = match read_request(&mut reader) {
+        Ok(request) => {
+            trace::info!("{} {}", request.method, request.path);
+            handle(registry, &request)
+        }
+        Err(err) if err.is::<BodyTooLarge>() => Response::error(413, &err.to_string()),
+        Err(err) if is_timeout(err.as_ref()) => Response::error(408, "request timed out"),
+        Err(err) => Response::error(400, &err.to_string()),
+    };
+    write_response(&mut &stream, &response)
+}
+
+/// `serve [--port N]` - serves `GET /tasks` and `POST /solve/{day}/{part}[?impl=NAME]` with
+/// the puzzle input as the body, on localhost only. Requests are handled one at a time, a
+/// client stalling for longer than `IO_TIMEOUT` gets a 408.
+pub fn run(registry: &TaskRegistry, args: &Args) -> Result<(), Box<dyn Error>> {
+    let port = args.option_or("port", DEFAULT_PORT)?;
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    println!("Listening on http://{}", listener.local_addr()?);
+
+    for stream in listener.incoming() {
+        if let Err(err) = stream
+            .map_err(Box::from)
+            .and_then(|stream| serve_connection(registry, stream, IO_TIMEOUT))
+        {
+            eprintln!("Error: {}", err);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Read, thread};
+
+    use crate::file_utils::read_to_string;
+
+    use super::*;
+
+    fn line_count(path: &str) -> Result<i64, Box<dyn Error>> {
+        Ok(read_to_string(path)?.lines().count() as i64)
+    }
+
+    fn word_count(path: &str) -> Result<i64, Box<dyn Error>> {
+        Ok(read_to_string(path)?.split_whitespace().count() as i64)
+    }
+
+    fn picky(_: &str) -> Result<i64, Box<dyn Error>> {
+        Err("bad input".into())
+    }
+
+    fn registry() -> TaskRegistry {
+        TaskRegistry::new(&[
+            ("day1_task1", line_count, "data/day1.txt"),
+            ("day1_task2", picky, "data/day1.txt"),
+        ])
+        .with_implementations(&[("day1_task1", "words", word_count)])
+    }
+
+    fn post(path: &str, query: Option<&str>, body: &str) -> Request {
+        Request {
+            method: "POST".to_string(),
+            path: path.to_string(),
+            query: query.map(str::to_string),
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn read_request_test() {
+        let raw = "POST /solve/1/2?impl=fast HTTP/1.1\r\nHost: localhost\r\n\
+                   content-length: 5\r\n\r\n1 2\n3";
+        let request = read_request(&mut raw.as_bytes()).unwrap();
+
+        assert_eq!(post("/solve/1/2", Some("impl=fast"), "1 2\n3"), request);
+    }
+
+    #[test]
+    fn handle_test() {
+        let registry = registry();
+
+        let response = handle(&registry, &post("/solve/1/1", None, "a b\nc\n"));
+        assert_eq!(200, response.status);
+        assert!(response.body.starts_with(
+            "{\"task_id\": \"day1_task1\", \"implementation\": \"default\", \"answer\": 2,"
+        ));
+
+        let response = handle(
+            &registry,
+            &post("/solve/1/1", Some("impl=words"), "a b\nc\n"),
+        );
+        assert!(response.body.contains("\"answer\": 3,"));
+
+        let response = handle(&registry, &post("/solve/1/2", None, ""));
+        assert_eq!(Response::error(422, "bad input"), response);
+
+        assert_eq!(404, handle(&registry, &post("/solve/9/1", None, "")).status);
+        assert_eq!(
+            404,
+            handle(&registry, &post("/solve/1/1", Some("impl=x"), "")).status
+        );
+        assert_eq!(405, handle(&registry, &post("/tasks", None, "")).status);
+    }
+
+    #[test]
+    fn list_tasks_test() {
+        assert_eq!(
+            "[{\"task_id\": \"day1_task1\", \"input_file\": \"data/day1.txt\", \
+             \"implementations\": [\"default\", \"words\"]}, \
+             {\"task_id\": \"day1_task2\", \"input_file\": \"data/day1.txt\", \
+             \"implementations\": [\"default\"]}]",
+            list_tasks(&registry()).body
+        );
+    }
+
+    #[test]
+    fn serve_connection_test() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            serve_connection(&registry(), stream, IO_TIMEOUT).unwrap();
+        });
+
+        let mut client = TcpStream::connect(addr).unwrap();
+        client
+            .write_all(b"POST /solve/1/1 HTTP/1.1\r\nContent-Length: 4\r\n\r\nx\ny\n")
+            .unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+        server.join().unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+        assert!(response.contains("\"answer\": 2,"), "{}", response);
+    }
+
+    #[test]
+    fn idle_client_times_out_test() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            serve_connection(&registry(), stream, Duration::from_millis(100)).unwrap();
+        });
+
+        // Sends the headers but never the promised body.
+        let mut client = TcpStream::connect(addr).unwrap();
+        client
+            .write_all(b"POST /solve/1/1 HTTP/1.1\r\nContent-Length: 4\r\n\r\n")
+            .unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+        server.join().unwrap();
+
+        assert!(
+            response.starts_with("HTTP/1.1 408 Request Timeout\r\n"),
+            "{}",
+            response
+        );
+    }
+}
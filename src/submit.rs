@@ -0,0 +1,343 @@
+use std::{
+    env,
+    error::Error,
+    io::Write,
+    process::{Command, Stdio},
+    time::Duration,
+};
+
+use crate::{
+    answers::{AnswerRecord, AnswersStore, Hint, Verdict, DEFAULT_ANSWERS_PATH},
+    args::Args,
+    file_utils::read_to_string,
+    lint,
+    task_registry::TaskRegistry,
+};
+
+const YEAR: u32 = 2024;
+
+#[derive(Debug, PartialEq)]
+pub enum SubmitOutcome {
+    Accepted,
+    Wrong(Option<Hint>),
+    Wait(Option<Duration>),
+}
+
+/// Posts an answer to the puzzle server and returns the raw response body.
+pub trait Transport {
+    fn post_answer(&self, day: u32, part: u32, answer: i64) -> Result<String, Box<dyn Error>>;
+}
+
+/// Talks to adventofcode.com through `curl`, authenticated with the session cookie.
+pub struct CurlTransport {
+    session: String,
+}
+
+impl CurlTransport {
+    pub fn from_env() -> Result<Self, Box<dyn Error>> {
+        let session = env::var("AOC_SESSION").map_err(|_| "AOC_SESSION is not set")?;
+        Ok(CurlTransport { session })
+    }
+}
+
+/// A curl config line setting the session cookie, quoted as curl expects.
+fn cookie_config(session: &str) -> String {
+    let escaped = session.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("cookie = \"session={}\"\n", escaped)
+}
+
+impl Transport for CurlTransport {
+    /// The session goes to curl as a config file on stdin, keeping it out of the argument list
+    /// that `ps` and shell history can see.
+    fn post_answer(&self, day: u32, part: u32, answer: i64) -> Result<String, Box<dyn Error>> {
+        let mut curl = Command::new("curl")
+            .arg("--silent")
+            .arg("--fail")
+            .arg("--config")
+            .arg("-")
+            .arg("--data")
+            .arg(format!("level={}&answer={}", part, answer))
+            .arg(format!(
+                "https://adventofcode.com/{}/day/{}/answer",
+                YEAR, day
+            ))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        // Dropping stdin closes it, so curl stops reading config and sends the request.
+        curl.stdin
+            .take()
+            .ok_or("curl stdin not captured")?
+            .write_all(cookie_config(&self.session).as_bytes())?;
+        let output = curl.wait_with_output()?;
+
+        if !output.status.success() {
+            return Err(format!("curl failed with {}", output.status).into());
+        }
+
+        Ok(String::from_utf8(output.stdout)?)
+    }
+}
+
+/// Parses `You have 1m 5s left to wait` into a duration.
+fn parse_wait(body: &str) -> Option<Duration> {
+    let (_, rest) = body.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+
+    let mut seconds = 0;
+    for part in wait.split_whitespace() {
+        let unit = part.chars().last()?;
+        let value: u64 = part.strip_suffix(unit)?.parse().ok()?;
+        seconds += match unit {
+            'h' => value * 3600,
+            'm' => value * 60,
+            's' => value,
+            _ => return None,
+        };
+    }
+
+    Some(Duration::from_secs(seconds))
+}
+
+pub fn parse_response(body: &str) -> Result<SubmitOutcome, Box<dyn Error>> {
+    if body.contains("That's the right answer") {
+        Ok(SubmitOutcome::Accepted)
+    } else if body.contains("That's not the right answer") {
+        let hint = if body.contains("your answer is too high") {
+            Some(Hint::TooHigh)
+        } else if body.contains("your answer is too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        Ok(SubmitOutcome::Wrong(hint))
+    } else if body.contains("You gave an answer too recently") {
+        Ok(SubmitOutcome::Wait(parse_wait(body)))
+    } else if body.contains("You don't seem to be solving the right level") {
+        Err("server says this level is already solved or locked".into())
+    } else {
+        Err("unrecognised response from server".into())
+    }
+}
+
+/// Checks the local guard rails, posts the answer and records the verdict.
+pub fn submit(
+    store: &mut AnswersStore,
+    transport: &dyn Transport,
+    day: u32,
+    part: u32,
+    answer: i64,
+) -> Result<SubmitOutcome, Box<dyn Error>> {
+    let task_id = format!("day{}_task{}", day, part);
+    store.check(&task_id, answer)?;
+
+    let body = transport.post_answer(day, part, answer)?;
+    let outcome = parse_response(&body)?;
+
+    let verdict = match outcome {
+        SubmitOutcome::Accepted => Some(Verdict::Accepted),
+        SubmitOutcome::Wrong(hint) => Some(Verdict::Wrong(hint)),
+        SubmitOutcome::Wait(_) => None,
+    };
+
+    if let Some(verdict) = verdict {
+        store.record(AnswerRecord {
+            task_id,
+            answer,
+            verdict,
+        })?;
+    }
+
+    Ok(outcome)
+}
+
+/// The real input of `day` to solve before submitting, `data/day<N>.txt` unless given.
+/// Sample inputs are refused, their answers would only count against the wrong answer limit.
+fn submission_input(day: u32, input_path: Option<&str>) -> Result<String, Box<dyn Error>> {
+    let input_path = input_path.map_or_else(|| format!("data/day{}.txt", day), String::from);
+    if input_path.ends_with("_test.txt") {
+        return Err(format!(
+            "{} is a sample input, submit answers for the real one",
+            input_path
+        )
+        .into());
+    }
+
+    if let Some(spec) = lint::spec(day) {
+        if lint::looks_like_sample(spec, &read_to_string(&input_path)?) {
+            return Err(format!(
+                "{} looks like the sample input rather than the real one",
+                input_path
+            )
+            .into());
+        }
+    }
+    Ok(input_path)
+}
+
+/// `submit <day> <part> [--input PATH]` - solves the real input and submits the result.
+pub fn run(registry: &TaskRegistry, args: &Args) -> Result<(), Box<dyn Error>> {
+    let (day, part): (u32, u32) = match args.positional() {
+        [day, part] => (day.parse()?, part.parse()?),
+        _ => return Err("usage: submit <day> <part> [--input PATH]".into()),
+    };
+
+    let task_id = format!("day{}_task{}", day, part);
+    let task = registry
+        .get(&task_id)
+        .ok_or_else(|| format!("Invalid task_id: {}", task_id))?;
+
+    let input_path = submission_input(day, args.option("input"))?;
+    lint::warn_about_input(registry, task, &input_path);
+    println!("Running task_id: {} on {}", task_id, input_path);
+    let answer = (task.default_implementation().func)(&input_path)?;
+    println!("Submitting: {}", answer);
+
+    let mut store = AnswersStore::load(DEFAULT_ANSWERS_PATH)?;
+    let transport = CurlTransport::from_env()?;
+
+    match submit(&mut store, &transport, day, part, answer)? {
+        SubmitOutcome::Accepted => println!("Accepted!"),
+        SubmitOutcome::Wrong(None) => println!("Wrong answer."),
+        SubmitOutcome::Wrong(Some(Hint::TooHigh)) => println!("Wrong answer, too high."),
+        SubmitOutcome::Wrong(Some(Hint::TooLow)) => println!("Wrong answer, too low."),
+        SubmitOutcome::Wait(Some(wait)) => println!("Too soon, wait {}s.", wait.as_secs()),
+        SubmitOutcome::Wait(None) => println!("Too soon, wait a bit."),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, fs};
+
+    use super::*;
+
+    struct StubTransport {
+        body: &'static str,
+        calls: RefCell<Vec<(u32, u32, i64)>>,
+    }
+
+    impl StubTransport {
+        fn new(body: &'static str) -> Self {
+            StubTransport {
+                body,
+                calls: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl Transport for StubTransport {
+        fn post_answer(&self, day: u32, part: u32, answer: i64) -> Result<String, Box<dyn Error>> {
+            self.calls.borrow_mut().push((day, part, answer));
+            Ok(self.body.to_string())
+        }
+    }
+
+    fn temp_store(name: &str) -> AnswersStore {
+        let path = env::temp_dir().join(format!("aoc2024_{}_{}.txt", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        AnswersStore::load(path).unwrap()
+    }
+
+    #[test]
+    fn parse_response_test() {
+        assert_eq!(
+            SubmitOutcome::Accepted,
+            parse_response("<p>That's the right answer! You are one gold star closer.</p>")
+                .unwrap()
+        );
+        assert_eq!(
+            SubmitOutcome::Wrong(Some(Hint::TooLow)),
+            parse_response("<p>That's not the right answer; your answer is too low.</p>").unwrap()
+        );
+        assert_eq!(
+            SubmitOutcome::Wrong(None),
+            parse_response("<p>That's not the right answer.</p>").unwrap()
+        );
+        assert_eq!(
+            SubmitOutcome::Wait(Some(Duration::from_secs(65))),
+            parse_response("You gave an answer too recently. You have 1m 5s left to wait.")
+                .unwrap()
+        );
+        assert!(parse_response("<html>Puzzle inputs differ by user.</html>").is_err());
+    }
+
+    #[test]
+    fn parse_wait_test() {
+        assert_eq!(
+            Some(Duration::from_secs(3725)),
+            parse_wait("You have 1h 2m 5s left to wait.")
+        );
+        assert_eq!(None, parse_wait("You have 5秒 left to wait."));
+        assert_eq!(None, parse_wait("You have 5x left to wait."));
+    }
+
+    #[test]
+    fn submission_input_test() {
+        assert_eq!("data/day3.txt", submission_input(3, None).unwrap());
+        assert_eq!(
+            "data/day7.txt",
+            submission_input(7, Some("data/day7.txt")).unwrap()
+        );
+        assert_eq!(
+            "data/day7_test.txt is a sample input, submit answers for the real one",
+            submission_input(7, Some("data/day7_test.txt"))
+                .unwrap_err()
+                .to_string()
+        );
+
+        let path = std::env::temp_dir().join(format!("aoc2024_submit_{}.txt", std::process::id()));
+        fs::write(&path, "3   4\n4   3\n").unwrap();
+        let path = path.to_str().unwrap();
+        assert!(submission_input(1, Some(path))
+            .unwrap_err()
+            .to_string()
+            .ends_with("looks like the sample input rather than the real one"));
+        fs::remove_file(path).unwrap();
+        assert!(submission_input(1, Some(path)).is_err());
+    }
+
+    #[test]
+    fn cookie_config_test() {
+        assert_eq!("cookie = \"session=53616c74\"\n", cookie_config("53616c74"));
+        assert_eq!(
+            "cookie = \"session=a\\\"b\\\\c\"\n",
+            cookie_config("a\"b\\c")
+        );
+    }
+
+    #[test]
+    fn submit_records_verdict_test() {
+        let mut store = temp_store("submit_records_verdict");
+        let transport = StubTransport::new("That's not the right answer; your answer is too high.");
+
+        assert_eq!(
+            SubmitOutcome::Wrong(Some(Hint::TooHigh)),
+            submit(&mut store, &transport, 1, 2, 500).unwrap()
+        );
+
+        // Both a duplicate and anything above the known bound never reach the server.
+        assert!(submit(&mut store, &transport, 1, 2, 500).is_err());
+        assert!(submit(&mut store, &transport, 1, 2, 600).is_err());
+        assert_eq!(vec![(1, 2, 500)], *transport.calls.borrow());
+
+        let reloaded = AnswersStore::load(store.path()).unwrap();
+        assert_eq!(1, reloaded.records_for("day1_task2").count());
+    }
+
+    #[test]
+    fn submit_wait_is_not_recorded_test() {
+        let mut store = temp_store("submit_wait_is_not_recorded");
+        let transport =
+            StubTransport::new("You gave an answer too recently. You have 30s left to wait.");
+
+        assert_eq!(
+            SubmitOutcome::Wait(Some(Duration::from_secs(30))),
+            submit(&mut store, &transport, 3, 1, 42).unwrap()
+        );
+        assert_eq!(0, store.records_for("day3_task1").count());
+    }
+}
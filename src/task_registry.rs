@@ -1,34 +1,121 @@
-use std::{collections::HashMap, error::Error};
+use std::error::Error;
 
-pub type Task = fn() -> Result<i64, Box<dyn Error>>;
+pub type TaskFn = fn(&str) -> Result<i64, Box<dyn Error>>;
+
+pub const DEFAULT_IMPLEMENTATION: &str = "default";
+
+/// Extracts `6` out of `day6_task2`.
+pub fn day_of(task_id: &str) -> Result<u32, Box<dyn Error>> {
+    let day = task_id
+        .strip_prefix("day")
+        .and_then(|rest| rest.split('_').next())
+        .ok_or_else(|| format!("cannot find day in task_id: {}", task_id))?;
+    Ok(day.parse()?)
+}
+
+/// One named way of solving a task, e.g. `naive` or `fast`.
+pub struct Implementation {
+    pub name: String,
+    pub func: TaskFn,
+}
+
+pub struct Task {
+    pub id: String,
+    pub input_path: String,
+    /// The first one is the default implementation.
+    implementations: Vec<Implementation>,
+}
+
+impl Task {
+    pub fn run(&self) -> Result<i64, Box<dyn Error>> {
+        (self.default_implementation().func)(&self.input_path)
+    }
+
+    pub fn default_implementation(&self) -> &Implementation {
+        &self.implementations[0]
+    }
+
+    pub fn implementation(&self, name: &str) -> Option<&Implementation> {
+        self.implementations.iter().find(|i| i.name == name)
+    }
+
+    pub fn implementations(&self) -> &[Implementation] {
+        &self.implementations
+    }
+}
 
 pub struct TaskRegistry {
-    tasks: HashMap<String, Task>,
-    latest_task_id: String,
+    tasks: Vec<Task>,
 }
 
 impl TaskRegistry {
-    pub fn new(tasks_init: &[(&str, Task)]) -> Self {
-        let mut tasks = HashMap::<String, Task>::new();
-        for (task_id, task_fn) in tasks_init {
-            tasks.insert(task_id.to_string(), *task_fn);
-        }
-        let latest_task_id = tasks_init
-            .last()
-            .expect("tasks_init should have at least one task")
-            .0
-            .to_string();
-        TaskRegistry {
-            tasks,
-            latest_task_id,
+    pub fn new(tasks_init: &[(&str, TaskFn, &str)]) -> Self {
+        assert!(
+            !tasks_init.is_empty(),
+            "tasks_init should have at least one task"
+        );
+
+        let tasks = tasks_init
+            .iter()
+            .map(|(task_id, func, input_path)| Task {
+                id: task_id.to_string(),
+                input_path: input_path.to_string(),
+                implementations: vec![Implementation {
+                    name: DEFAULT_IMPLEMENTATION.to_string(),
+                    func: *func,
+                }],
+            })
+            .collect();
+
+        TaskRegistry { tasks }
+    }
+
+    /// Registers alternative `(task_id, name, func)` implementations of already known tasks.
+    pub fn with_implementations(mut self, implementations: &[(&str, &str, TaskFn)]) -> Self {
+        for (task_id, name, func) in implementations {
+            let task = self
+                .tasks
+                .iter_mut()
+                .find(|t| t.id == *task_id)
+                .unwrap_or_else(|| panic!("unknown task_id for implementation: {}", task_id));
+
+            assert!(
+                task.implementation(name).is_none(),
+                "duplicate implementation {} of {}",
+                name,
+                task_id
+            );
+
+            task.implementations.push(Implementation {
+                name: name.to_string(),
+                func: *func,
+            });
         }
+        self
     }
 
     pub fn get(&self, task_id: &str) -> Option<&Task> {
-        self.tasks.get(task_id)
+        self.tasks.iter().find(|t| t.id == task_id)
+    }
+
+    /// All tasks in registration order.
+    pub fn tasks(&self) -> &[Task] {
+        &self.tasks
     }
 
     pub fn latest_task_id(&self) -> &str {
-        &self.latest_task_id
+        &self.tasks.last().unwrap().id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_of_test() {
+        assert_eq!(6, day_of("day6_task2").unwrap());
+        assert_eq!(12, day_of("day12_task1").unwrap());
+        assert!(day_of("task1").is_err());
     }
 }
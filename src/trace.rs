@@ -0,0 +1,77 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Verbosity of diagnostics, each `-v` on the command line enables one more level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Info = 1,
+    Debug = 2,
+    Trace = 3,
+}
+
+impl Level {
+    pub fn name(self) -> &'static str {
+        match self {
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+}
+
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_verbosity(verbosity: u8) {
+    MAX_LEVEL.store(verbosity, Ordering::Relaxed);
+}
+
+/// Checked by the macros before any formatting happens, so disabled events only cost a load.
+#[inline(always)]
+pub fn enabled(level: Level) -> bool {
+    level as u8 <= MAX_LEVEL.load(Ordering::Relaxed)
+}
+
+/// Emits a diagnostic to stderr, tagged with the level and the emitting module.
+macro_rules! event {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::trace::enabled($level) {
+            eprintln!(
+                "[{}] {}: {}",
+                $level.name(),
+                module_path!().trim_start_matches("aoc2024::"),
+                format_args!($($arg)+)
+            );
+        }
+    };
+}
+
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::trace::event!($crate::trace::Level::Info, $($arg)+) };
+}
+
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::trace::event!($crate::trace::Level::Debug, $($arg)+) };
+}
+
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::trace::event!($crate::trace::Level::Trace, $($arg)+) };
+}
+
+pub(crate) use {debug, event, info, trace};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enabled_test() {
+        set_verbosity(0);
+        assert!(!enabled(Level::Info));
+
+        set_verbosity(2);
+        assert!(enabled(Level::Info));
+        assert!(enabled(Level::Debug));
+        assert!(!enabled(Level::Trace));
+
+        set_verbosity(0);
+    }
+}
@@ -0,0 +1,330 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    args::Args,
+    task_registry::{day_of, TaskRegistry},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+type Snapshot = HashMap<PathBuf, SystemTime>;
+
+/// Outcome of a single re-run, compared against the previous one.
+#[derive(Debug, Clone, PartialEq)]
+enum RunOutcome {
+    Answer {
+        answer: Result<i64, String>,
+        mean_ms: f64,
+    },
+    Tests {
+        passed: bool,
+        summary: String,
+    },
+    BuildFailed(String),
+}
+
+fn is_day_input(file_name: &str, day: u32) -> bool {
+    let prefix = format!("day{}", day);
+    match file_name.strip_prefix(&prefix) {
+        Some(rest) => rest == ".txt" || (rest.starts_with('_') && rest.ends_with(".txt")),
+        None => false,
+    }
+}
+
+/// Source file of the day (plus its submodule directory) and all of its inputs.
+fn watched_files(root: &Path, day: u32) -> Vec<PathBuf> {
+    let mut files = vec![root.join(format!("src/day{}.rs", day))];
+
+    if let Ok(entries) = fs::read_dir(root.join(format!("src/day{}", day))) {
+        files.extend(entries.flatten().map(|e| e.path()));
+    }
+
+    if let Ok(entries) = fs::read_dir(root.join("data")) {
+        files.extend(
+            entries
+                .flatten()
+                .filter(|e| is_day_input(&e.file_name().to_string_lossy(), day))
+                .map(|e| e.path()),
+        );
+    }
+
+    files.sort();
+    files
+}
+
+fn snapshot(files: &[PathBuf]) -> Snapshot {
+    files
+        .iter()
+        .filter_map(|path| {
+            let modified = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+            Some((path.clone(), modified))
+        })
+        .collect()
+}
+
+/// Splits a single CSV record, honouring quoted fields.
+fn parse_csv_record(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            ('"', _) => in_quotes = !in_quotes,
+            (',', false) => fields.push(String::new()),
+            (c, _) => fields.last_mut().unwrap().push(c),
+        }
+    }
+
+    fields
+}
+
+/// Reads the answer out of `run --format csv` output.
+fn parse_run_output(stdout: &str) -> Result<RunOutcome, Box<dyn Error>> {
+    let mut lines = stdout.lines().map(parse_csv_record);
+    let header = lines.next().ok_or("no header in task output")?;
+    let record = lines.next().ok_or("no report in task output")?;
+
+    let field = |name: &str| -> Result<&String, Box<dyn Error>> {
+        header
+            .iter()
+            .position(|h| h == name)
+            .and_then(|idx| record.get(idx))
+            .ok_or_else(|| format!("no {} in report: {:?}", name, record).into())
+    };
+    let (answer, error, mean_ms) = (field("answer")?, field("error")?, field("mean_ms")?);
+
+    let answer = if error.is_empty() {
+        Ok(answer.parse()?)
+    } else {
+        Err(error.clone())
+    };
+
+    Ok(RunOutcome::Answer {
+        answer,
+        mean_ms: mean_ms.parse()?,
+    })
+}
+
+fn last_lines(output: &[u8], count: usize) -> String {
+    let output = String::from_utf8_lossy(output);
+    let lines: Vec<_> = output.lines().collect();
+    lines[lines.len().saturating_sub(count)..].join("\n")
+}
+
+/// Rebuilds and re-runs the task (or the day's tests) in a child cargo process.
+fn run_once(task_id: &str, day: u32, tests: bool) -> Result<RunOutcome, Box<dyn Error>> {
+    if tests {
+        let output = Command::new("cargo")
+            .args(["test", "--quiet", &format!("day{}::", day)])
+            .output()?;
+        let summary = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .rfind(|l| l.starts_with("test result:"))
+            .map(str::to_string);
+
+        return Ok(match summary {
+            Some(summary) => RunOutcome::Tests {
+                passed: output.status.success(),
+                summary,
+            },
+            None => RunOutcome::BuildFailed(last_lines(&output.stderr, 20)),
+        });
+    }
+
+    let output = Command::new("cargo")
+        .args(["run", "--quiet", "--release", "--", "run", task_id])
+        .args(["--format", "csv"])
+        .output()?;
+
+    match parse_run_output(&String::from_utf8_lossy(&output.stdout)) {
+        Ok(outcome) => Ok(outcome),
+        Err(_) => Ok(RunOutcome::BuildFailed(last_lines(&output.stderr, 20))),
+    }
+}
+
+fn describe_change(previous: Option<&RunOutcome>, current: &RunOutcome) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    match (previous, current) {
+        (
+            Some(RunOutcome::Answer {
+                answer: prev_answer,
+                mean_ms: prev_ms,
+            }),
+            RunOutcome::Answer { answer, mean_ms },
+        ) => {
+            if prev_answer == answer {
+                lines.push(format!("Result: {} (unchanged)", show_answer(answer)));
+            } else {
+                lines.push(format!(
+                    "Result: {} -> {}",
+                    show_answer(prev_answer),
+                    show_answer(answer)
+                ));
+            }
+
+            let change = if *prev_ms > 0.0 {
+                format!(" ({:+.1}%)", (mean_ms - prev_ms) / prev_ms * 100.0)
+            } else {
+                String::new()
+            };
+            lines.push(format!(
+                "Time: {:.3}ms -> {:.3}ms{}",
+                prev_ms, mean_ms, change
+            ));
+        }
+        (_, RunOutcome::Answer { answer, mean_ms }) => {
+            lines.push(format!("Result: {}", show_answer(answer)));
+            lines.push(format!("Time: {:.3}ms", mean_ms));
+        }
+        (Some(RunOutcome::Tests { passed: was, .. }), RunOutcome::Tests { passed, summary })
+            if was != passed =>
+        {
+            let status = if *passed {
+                "now passing"
+            } else {
+                "now failing"
+            };
+            lines.push(format!("{} ({})", summary, status));
+        }
+        (_, RunOutcome::Tests { summary, .. }) => lines.push(summary.clone()),
+        (_, RunOutcome::BuildFailed(stderr)) => {
+            lines.push("Build or run failed:".to_string());
+            lines.push(stderr.clone());
+        }
+    }
+
+    lines
+}
+
+fn show_answer(answer: &Result<i64, String>) -> String {
+    match answer {
+        Ok(answer) => answer.to_string(),
+        Err(err) => format!("error: {}", err),
+    }
+}
+
+/// `watch <task_id> [--tests]` - re-runs the task whenever its source or inputs change.
+pub fn run(registry: &TaskRegistry, args: &Args) -> Result<(), Box<dyn Error>> {
+    let task_id = match args.positional().first() {
+        Some(task_id) => task_id.as_str(),
+        None => registry.latest_task_id(),
+    };
+    registry
+        .get(task_id)
+        .ok_or_else(|| format!("Invalid task_id: {}", task_id))?;
+
+    let day = day_of(task_id)?;
+    let tests = args.switch("tests");
+    let root = Path::new(".");
+
+    let mut previous: Option<RunOutcome> = None;
+    let mut last_snapshot: Option<Snapshot> = None;
+
+    loop {
+        let current_snapshot = snapshot(&watched_files(root, day));
+
+        if last_snapshot.as_ref() != Some(&current_snapshot) {
+            println!(
+                "Watching {} file(s) for day{}, re-running {}...",
+                current_snapshot.len(),
+                day,
+                if tests { "tests" } else { task_id }
+            );
+
+            let outcome = run_once(task_id, day, tests)?;
+            for line in describe_change(previous.as_ref(), &outcome) {
+                println!("{}", line);
+            }
+
+            previous = Some(outcome);
+            last_snapshot = Some(current_snapshot);
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_day_input_test() {
+        assert!(is_day_input("day1.txt", 1));
+        assert!(is_day_input("day1_test.txt", 1));
+        assert!(!is_day_input("day10.txt", 1));
+        assert!(!is_day_input("day10_test.txt", 1));
+        assert!(!is_day_input("day1.rs", 1));
+    }
+
+    #[test]
+    fn watched_files_test() {
+        let files = watched_files(Path::new("."), 3);
+
+        assert!(files.contains(&PathBuf::from("./src/day3.rs")));
+        assert!(files.contains(&PathBuf::from("./data/day3_test2.txt")));
+        assert!(!files.contains(&PathBuf::from("./data/day2.txt")));
+    }
+
+    #[test]
+    fn parse_run_output_test() {
+        assert_eq!(
+            RunOutcome::Answer {
+                answer: Ok(41),
+                mean_ms: 0.5
+            },
+            parse_run_output(
+                "task_id,input_file,answer,error,runs,min_ms,mean_ms,max_ms\n\
+                 day6_task1,data/day6_test.txt,41,,1,0.500,0.500,0.500\n"
+            )
+            .unwrap()
+        );
+
+        assert_eq!(
+            RunOutcome::Answer {
+                answer: Err("bad, \"input\"".to_string()),
+                mean_ms: 0.1
+            },
+            parse_run_output(
+                "task_id,input_file,answer,error,runs,min_ms,mean_ms,max_ms\n\
+                 day6_task1,data/day6_test.txt,,\"bad, \"\"input\"\"\",1,0.100,0.100,0.100\n"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn describe_change_test() {
+        let before = RunOutcome::Answer {
+            answer: Ok(41),
+            mean_ms: 2.0,
+        };
+        let after = RunOutcome::Answer {
+            answer: Ok(42),
+            mean_ms: 1.0,
+        };
+
+        assert_eq!(
+            vec!["Result: 41 -> 42", "Time: 2.000ms -> 1.000ms (-50.0%)"],
+            describe_change(Some(&before), &after)
+        );
+        assert_eq!(
+            vec!["Result: 41", "Time: 2.000ms"],
+            describe_change(None, &before)
+        );
+    }
+}